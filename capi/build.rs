@@ -0,0 +1,16 @@
+use std::path::Path;
+
+fn main() {
+    // generate into OUT_DIR so builds never touch the source tree, a test
+    // checks that the committed include/aoc_2023.h matches
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let config = cbindgen::Config::from_file(Path::new(&crate_dir).join("cbindgen.toml"))
+        .expect("read cbindgen.toml");
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("generate C header")
+        .write_to_file(Path::new(&out_dir).join("aoc_2023.h"));
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
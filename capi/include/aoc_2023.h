@@ -0,0 +1,75 @@
+#ifndef AOC_2023_H
+#define AOC_2023_H
+
+/* Generated by cbindgen from capi/src/lib.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Result of a call into the library.
+typedef enum AocStatus {
+  // The answer was written to the output buffer.
+  AOC_STATUS_OK = 0,
+  // The input or output pointer was null.
+  AOC_STATUS_NULL_POINTER = 1,
+  // The input is not valid UTF-8.
+  AOC_STATUS_INVALID_UTF8 = 2,
+  // There is no solver for the requested day.
+  AOC_STATUS_UNKNOWN_DAY = 3,
+  // The part was not 1 or 2.
+  AOC_STATUS_UNKNOWN_PART = 4,
+  // The day's solver rejected the input, see `aoc_last_error`.
+  AOC_STATUS_INVALID_INPUT = 5,
+  // The day exists but this part has not been solved yet.
+  AOC_STATUS_NOT_IMPLEMENTED = 6,
+  // The output buffer cannot hold the answer and its terminating NUL.
+  AOC_STATUS_BUFFER_TOO_SMALL = 7,
+  // Day 3: the answer does not fit in an `i64`.
+  AOC_STATUS_DAY03_OVERFLOW = 301,
+  // Day 4: the part one score does not fit in a `u64`.
+  AOC_STATUS_DAY04_SCORE_TOO_LARGE = 401,
+  // Day 6: part two has more ways to win than fit in a `u64`.
+  AOC_STATUS_DAY06_TOO_MANY_WAYS = 601,
+} AocStatus;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Solve `part` (1 or 2) of `day` for the `input_len` bytes of UTF-8 text at
+// `input_ptr`, writing the answer as a NUL terminated decimal string into
+// `out_buf`. On anything other than `AOC_STATUS_OK` the output buffer is
+// left untouched and `aoc_last_error` describes what went wrong.
+//
+// # Safety
+//
+// `input_ptr` must be valid for reads of `input_len` bytes (it may be null
+// when `input_len` is 0) and `out_buf` must be valid for writes of `out_len`
+// bytes.
+enum AocStatus aoc_solve(uint32_t day,
+                         uint32_t part,
+                         const uint8_t *input_ptr,
+                         size_t input_len,
+                         char *out_buf,
+                         size_t out_len);
+
+// Copy the message for the last failed call on this thread into `out_buf`,
+// truncating it to fit. Returns the length of the full message, excluding
+// the NUL, so callers can retry with a larger buffer.
+//
+// # Safety
+//
+// `out_buf` must be null or valid for writes of `out_len` bytes.
+size_t aoc_last_error(char *out_buf, size_t out_len);
+
+// Static description of a status code, for logging.
+const char *aoc_status_message(int32_t status);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC_2023_H */
@@ -0,0 +1,391 @@
+//! C ABI for the solvers, so other programs can call them without shelling
+//! out to the binaries. The header lives in `include/aoc_2023.h`. cbindgen
+//! regenerates it into `OUT_DIR` on every build and a test fails when the
+//! committed copy is out of date.
+//!
+//! ```c
+//! char answer[32];
+//! AocStatus status = aoc_solve(1, 1, input, input_len, answer, sizeof answer);
+//! if (status != AOC_STATUS_OK) {
+//!     char message[256];
+//!     aoc_last_error(message, sizeof message);
+//! }
+//! ```
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::{ptr, slice, str};
+
+/// Result of a call into the library.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AocStatus {
+    /// The answer was written to the output buffer.
+    Ok = 0,
+    /// The input or output pointer was null.
+    NullPointer = 1,
+    /// The input is not valid UTF-8.
+    InvalidUtf8 = 2,
+    /// There is no solver for the requested day.
+    UnknownDay = 3,
+    /// The part was not 1 or 2.
+    UnknownPart = 4,
+    /// The day's solver rejected the input, see `aoc_last_error`.
+    InvalidInput = 5,
+    /// The day exists but this part has not been solved yet.
+    NotImplemented = 6,
+    /// The output buffer cannot hold the answer and its terminating NUL.
+    BufferTooSmall = 7,
+    /// Day 3: the answer does not fit in an `i64`.
+    Day03Overflow = 301,
+    /// Day 4: the part one score does not fit in a `u64`.
+    Day04ScoreTooLarge = 401,
+    /// Day 6: part two has more ways to win than fit in a `u64`.
+    Day06TooManyWays = 601,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<String> = const { RefCell::new(String::new()) };
+}
+
+/// Part two of day 11 as asked in the puzzle.
+const DAY11_EXPANSION: usize = 1_000_000;
+
+/// The `(day, part)`s whose solver is still a `todo!()`.
+const UNIMPLEMENTED: [(u32, u32); 2] = [(13, 2), (14, 2)];
+
+/// A failure a day's solver reports without panicking.
+struct Failure {
+    status: AocStatus,
+    message: String,
+}
+
+impl Failure {
+    /// `status` for a day specific error, `InvalidInput` for anything else.
+    fn new(is_specific: bool, status: AocStatus, error: impl fmt::Display) -> Failure {
+        Failure {
+            status: if is_specific {
+                status
+            } else {
+                AocStatus::InvalidInput
+            },
+            message: error.to_string(),
+        }
+    }
+}
+
+fn answer<T: ToString, E>(
+    result: Result<T, E>,
+    to_failure: impl FnOnce(E) -> Failure,
+) -> Option<Result<String, Failure>> {
+    Some(result.map(|answer| answer.to_string()).map_err(to_failure))
+}
+
+fn solve(day: u32, part: u32, input: &str) -> Result<String, AocStatus> {
+    use day03::day03::{Rules, SchematicError};
+    use day04::day04::ScoreError;
+    use day06::day06::KernedRaceError;
+
+    if !(1..=2).contains(&part) {
+        return Err(AocStatus::UnknownPart);
+    }
+    if UNIMPLEMENTED.contains(&(day, part)) {
+        set_last_error(format!("day {:02} part {} is not solved yet", day, part));
+        return Err(AocStatus::NotImplemented);
+    }
+    let part_one = part == 1;
+    let day03 = |e: SchematicError| {
+        let overflow = e == SchematicError::Overflow;
+        Failure::new(overflow, AocStatus::Day03Overflow, e)
+    };
+    let answer = panic::catch_unwind(AssertUnwindSafe(|| match day {
+        // days with typed errors map them to their own status codes
+        3 if part_one => answer(
+            day03::day03::solve_part_one_with_rules(input, &Rules::default()),
+            day03,
+        ),
+        3 => answer(
+            day03::day03::solve_part_two_with_rules(input, &Rules::default()),
+            day03,
+        ),
+        4 if part_one => answer(day04::day04::try_solve_part_one(input), |e| {
+            let too_large = e == ScoreError::TooLarge;
+            Failure::new(too_large, AocStatus::Day04ScoreTooLarge, e)
+        }),
+        6 if !part_one => answer(day06::day06::try_solve_part_two(input), |e| {
+            let too_many = matches!(e, KernedRaceError::TooManyWays(_));
+            Failure::new(too_many, AocStatus::Day06TooManyWays, e)
+        }),
+        // the others panic on bad input
+        _ => solve_or_panic(day, part_one, input).map(Ok),
+    }));
+
+    match answer {
+        Ok(Some(Ok(answer))) => Ok(answer),
+        Ok(Some(Err(failure))) => {
+            set_last_error(format!("day {:02} part {}: {}", day, part, failure.message));
+            Err(failure.status)
+        }
+        Ok(None) => Err(AocStatus::UnknownDay),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_else(|| "solver panicked".to_string());
+            set_last_error(format!("day {:02} part {}: {}", day, part, message));
+            Err(AocStatus::InvalidInput)
+        }
+    }
+}
+
+/// The parts `solve` has no typed errors for. Day 3, day 4 part one, day 6
+/// part two and the `UNIMPLEMENTED` parts never get here.
+fn solve_or_panic(day: u32, part_one: bool, input: &str) -> Option<String> {
+    match day {
+        1 if part_one => Some(day01::day01::solve_part_one(input).to_string()),
+        1 => Some(day01::day01::solve_part_two(input).to_string()),
+        2 if part_one => Some(day02::day02::solve_part_one(input).to_string()),
+        2 => Some(day02::day02::solve_part_two(input).to_string()),
+        4 if !part_one => Some(day04::day04::solve_part_two(input).to_string()),
+        5 if part_one => Some(day05::day05::solve_part_one(input).to_string()),
+        5 => Some(day05::day05::solve_part_two(input).to_string()),
+        6 if part_one => Some(day06::day06::solve_part_one(input).to_string()),
+        7 if part_one => Some(day07::day07::solve_part_one(input).to_string()),
+        7 => Some(day07::day07::solve_part_two(input).to_string()),
+        8 if part_one => Some(day08::day08::solve_part_one(input).to_string()),
+        8 => Some(day08::day08::solve_part_two(input).to_string()),
+        9 if part_one => Some(day09::day09::solve_part_one(input).to_string()),
+        9 => Some(day09::day09::solve_part_two(input).to_string()),
+        10 if part_one => Some(day10::day10::solve_part_one(input).to_string()),
+        10 => Some(day10::day10::solve_part_two(input).to_string()),
+        11 if part_one => Some(day11::day11::solve_part_one(input).to_string()),
+        11 => Some(day11::day11::solve_part_two(input, DAY11_EXPANSION).to_string()),
+        12 if part_one => Some(day12::day12::solve_part_one(input).to_string()),
+        12 => Some(day12::day12::solve_part_two(input).to_string()),
+        13 if part_one => Some(day13::day13::solve_part_one(input).to_string()),
+        14 if part_one => Some(day14::day14::solve_part_one(input).to_string()),
+        _ => None,
+    }
+}
+
+fn set_last_error(message: String) {
+    LAST_ERROR.with(|e| *e.borrow_mut() = message);
+}
+
+/// Copy `s` and a terminating NUL into `out_buf`.
+///
+/// # Safety
+///
+/// `out_buf` must be valid for writes of `out_len` bytes.
+unsafe fn write_c_str(s: &str, out_buf: *mut c_char, out_len: usize) -> AocStatus {
+    if s.len() >= out_len {
+        return AocStatus::BufferTooSmall;
+    }
+    ptr::copy_nonoverlapping(s.as_ptr(), out_buf.cast::<u8>(), s.len());
+    *out_buf.add(s.len()) = 0;
+    AocStatus::Ok
+}
+
+/// Solve `part` (1 or 2) of `day` for the `input_len` bytes of UTF-8 text at
+/// `input_ptr`, writing the answer as a NUL terminated decimal string into
+/// `out_buf`. On anything other than `AOC_STATUS_OK` the output buffer is
+/// left untouched and `aoc_last_error` describes what went wrong.
+///
+/// # Safety
+///
+/// `input_ptr` must be valid for reads of `input_len` bytes (it may be null
+/// when `input_len` is 0) and `out_buf` must be valid for writes of `out_len`
+/// bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    part: u32,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut c_char,
+    out_len: usize,
+) -> AocStatus {
+    if out_buf.is_null() || (input_ptr.is_null() && input_len > 0) {
+        set_last_error("null pointer passed to aoc_solve".to_string());
+        return AocStatus::NullPointer;
+    }
+    let bytes = if input_len == 0 {
+        &[][..]
+    } else {
+        slice::from_raw_parts(input_ptr, input_len)
+    };
+    let input = match str::from_utf8(bytes) {
+        Ok(input) => input,
+        Err(e) => {
+            set_last_error(format!("input is not valid UTF-8: {}", e));
+            return AocStatus::InvalidUtf8;
+        }
+    };
+
+    match solve(day, part, input) {
+        Ok(answer) => {
+            let status = write_c_str(&answer, out_buf, out_len);
+            if status == AocStatus::BufferTooSmall {
+                set_last_error(format!(
+                    "answer {} needs a buffer of {} bytes",
+                    answer,
+                    answer.len() + 1
+                ));
+            }
+            status
+        }
+        Err(AocStatus::UnknownDay) => {
+            set_last_error(format!("no solver for day {}", day));
+            AocStatus::UnknownDay
+        }
+        Err(AocStatus::UnknownPart) => {
+            set_last_error(format!("part must be 1 or 2, not {}", part));
+            AocStatus::UnknownPart
+        }
+        Err(status) => status,
+    }
+}
+
+/// Copy the message for the last failed call on this thread into `out_buf`,
+/// truncating it to fit. Returns the length of the full message, excluding
+/// the NUL, so callers can retry with a larger buffer.
+///
+/// # Safety
+///
+/// `out_buf` must be null or valid for writes of `out_len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_last_error(out_buf: *mut c_char, out_len: usize) -> usize {
+    LAST_ERROR.with(|e| {
+        let message = e.borrow();
+        if !out_buf.is_null() && out_len > 0 {
+            let n = message.len().min(out_len - 1);
+            ptr::copy_nonoverlapping(message.as_ptr(), out_buf.cast::<u8>(), n);
+            *out_buf.add(n) = 0;
+        }
+        message.len()
+    })
+}
+
+/// Static description of a status code, for logging.
+#[no_mangle]
+pub extern "C" fn aoc_status_message(status: i32) -> *const c_char {
+    let message: &'static CStr = match status {
+        0 => c"ok",
+        1 => c"null pointer",
+        2 => c"input is not valid UTF-8",
+        3 => c"unknown day",
+        4 => c"unknown part",
+        5 => c"invalid input",
+        6 => c"not implemented",
+        7 => c"output buffer too small",
+        301 => c"day 3 answer does not fit in an i64",
+        401 => c"day 4 score does not fit in a u64",
+        601 => c"day 6 has too many ways to win for a u64",
+        _ => c"unknown status",
+    };
+    message.as_ptr()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY04_EXAMPLE: &str = include_str!("../../examples/day04/example.in");
+
+    fn call(day: u32, part: u32, input: &str, out_len: usize) -> (AocStatus, String) {
+        let mut out = vec![0 as c_char; out_len.max(1)];
+        let status = unsafe {
+            aoc_solve(
+                day,
+                part,
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr(),
+                out_len,
+            )
+        };
+        let answer = unsafe { CStr::from_ptr(out.as_ptr()) };
+        (status, answer.to_string_lossy().to_string())
+    }
+
+    fn last_error() -> String {
+        let mut out = [0 as c_char; 256];
+        unsafe { aoc_last_error(out.as_mut_ptr(), out.len()) };
+        unsafe { CStr::from_ptr(out.as_ptr()) }
+            .to_string_lossy()
+            .to_string()
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            call(4, 1, DAY04_EXAMPLE, 16),
+            (AocStatus::Ok, "13".to_string())
+        );
+        assert_eq!(
+            call(4, 2, DAY04_EXAMPLE, 16),
+            (AocStatus::Ok, "30".to_string())
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(call(26, 1, DAY04_EXAMPLE, 16).0, AocStatus::UnknownDay);
+        assert_eq!(last_error(), "no solver for day 26");
+        assert_eq!(call(4, 3, DAY04_EXAMPLE, 16).0, AocStatus::UnknownPart);
+        assert_eq!(call(13, 2, "#.\n.#\n", 16).0, AocStatus::NotImplemented);
+        assert_eq!(last_error(), "day 13 part 2 is not solved yet");
+
+        // "13" and the NUL need three bytes, and the buffer is left alone
+        assert_eq!(
+            call(4, 1, DAY04_EXAMPLE, 2),
+            (AocStatus::BufferTooSmall, String::new())
+        );
+        assert_eq!(call(4, 1, DAY04_EXAMPLE, 3).0, AocStatus::Ok);
+
+        assert_eq!(
+            call(6, 1, "Time: 7\nDistance 9", 16).0,
+            AocStatus::InvalidInput
+        );
+        assert_eq!(
+            last_error(),
+            "day 06 part 1: line 2, column 10: expected ':' in Distance"
+        );
+
+        // day specific errors have their own codes
+        assert_eq!(
+            call(3, 2, "9223372036854775807*2", 16).0,
+            AocStatus::Day03Overflow
+        );
+        assert_eq!(last_error(), "day 03 part 2: answer does not fit in an i64");
+        assert_eq!(call(3, 1, "1\n2x", 16).0, AocStatus::Ok);
+        let numbers: Vec<String> = (1..=65).map(|n| n.to_string()).collect();
+        let card = format!("Card 1: {} | {}", numbers.join(" "), numbers.join(" "));
+        assert_eq!(call(4, 1, &card, 16).0, AocStatus::Day04ScoreTooLarge);
+        assert_eq!(call(4, 1, "Card 1: x", 16).0, AocStatus::InvalidInput);
+        let races = "Time: 99999999999 99999999999\nDistance: 0 0";
+        assert_eq!(call(6, 2, races, 16).0, AocStatus::Day06TooManyWays);
+        assert_eq!(call(6, 2, "Time: 7", 16).0, AocStatus::InvalidInput);
+
+        let invalid = [0xff, 0xfe];
+        let mut out = [0 as c_char; 16];
+        let status = unsafe { aoc_solve(1, 1, invalid.as_ptr(), 2, out.as_mut_ptr(), 16) };
+        assert_eq!(status, AocStatus::InvalidUtf8);
+        let status = unsafe { aoc_solve(1, 1, ptr::null(), 2, out.as_mut_ptr(), 16) };
+        assert_eq!(status, AocStatus::NullPointer);
+    }
+
+    #[test]
+    fn test_header_is_up_to_date() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc_2023.h"));
+        let committed = include_str!("../include/aoc_2023.h");
+        assert!(
+            generated == committed,
+            "include/aoc_2023.h is out of date, copy it from {}/aoc_2023.h",
+            env!("OUT_DIR")
+        );
+    }
+}
@@ -0,0 +1,104 @@
+/* Exercises the C header and library the way an embedding program would.
+ * Built and run by tests/c_api.rs, prints the first failed check and exits
+ * non-zero on failure. */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "aoc_2023.h"
+
+static int failures = 0;
+
+#define CHECK(cond)                                                    \
+    do {                                                               \
+        if (!(cond)) {                                                 \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__,     \
+                    __LINE__, #cond);                                  \
+            failures++;                                                \
+        }                                                              \
+    } while (0)
+
+static char *read_file(const char *path, size_t *len) {
+    FILE *f = fopen(path, "rb");
+    if (f == NULL) {
+        perror(path);
+        exit(2);
+    }
+    fseek(f, 0, SEEK_END);
+    *len = (size_t)ftell(f);
+    fseek(f, 0, SEEK_SET);
+    char *buf = malloc(*len);
+    if (fread(buf, 1, *len, f) != *len) {
+        perror(path);
+        exit(2);
+    }
+    fclose(f);
+    return buf;
+}
+
+static void check_answer(const char *examples, const char *file, uint32_t day,
+                         uint32_t part, const char *expected) {
+    char path[1024];
+    snprintf(path, sizeof path, "%s/%s", examples, file);
+    size_t len;
+    char *input = read_file(path, &len);
+
+    char answer[32];
+    AocStatus status = aoc_solve(day, part, (const uint8_t *)input, len,
+                                 answer, sizeof answer);
+    if (status != AOC_STATUS_OK) {
+        char message[256];
+        aoc_last_error(message, sizeof message);
+        fprintf(stderr, "day %u part %u: %s (%s)\n", day, part,
+                aoc_status_message(status), message);
+    }
+    CHECK(status == AOC_STATUS_OK);
+    CHECK(strcmp(answer, expected) == 0);
+    free(input);
+}
+
+int main(int argc, char **argv) {
+    if (argc != 2) {
+        fprintf(stderr, "usage: %s <examples dir>\n", argv[0]);
+        return 2;
+    }
+    const char *examples = argv[1];
+
+    check_answer(examples, "day01/example_one.in", 1, 1, "142");
+    check_answer(examples, "day01/example_two.in", 1, 2, "281");
+    check_answer(examples, "day04/example.in", 4, 1, "13");
+    check_answer(examples, "day04/example.in", 4, 2, "30");
+    check_answer(examples, "day07/example.in", 7, 2, "5905");
+    check_answer(examples, "day11/example.in", 11, 2, "82000210");
+
+    char answer[32] = "untouched";
+    const char *game = "Game 1: 3 blue, 4 red\n";
+    CHECK(aoc_solve(0, 1, (const uint8_t *)game, strlen(game), answer,
+                    sizeof answer) == AOC_STATUS_UNKNOWN_DAY);
+    CHECK(aoc_solve(2, 3, (const uint8_t *)game, strlen(game), answer,
+                    sizeof answer) == AOC_STATUS_UNKNOWN_PART);
+    CHECK(aoc_solve(2, 1, (const uint8_t *)game, strlen(game), answer, 1) ==
+          AOC_STATUS_BUFFER_TOO_SMALL);
+    CHECK(strcmp(answer, "untouched") == 0);
+    CHECK(aoc_solve(2, 1, NULL, 4, answer, sizeof answer) ==
+          AOC_STATUS_NULL_POINTER);
+
+    const char *races = "Time: 7\nDistance 9";
+    CHECK(aoc_solve(6, 1, (const uint8_t *)races, strlen(races), answer,
+                    sizeof answer) == AOC_STATUS_INVALID_INPUT);
+    char message[8];
+    size_t full = aoc_last_error(message, sizeof message);
+    CHECK(full > sizeof message);
+    CHECK(strlen(message) == sizeof message - 1);
+
+    CHECK(strcmp(aoc_status_message(AOC_STATUS_NOT_IMPLEMENTED),
+                 "not implemented") == 0);
+    CHECK(strcmp(aoc_status_message(AOC_STATUS_DAY04_SCORE_TOO_LARGE),
+                 "day 4 score does not fit in a u64") == 0);
+
+    if (failures == 0) {
+        printf("all C API checks passed\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
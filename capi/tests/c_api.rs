@@ -0,0 +1,47 @@
+//! Compiles `tests/c/test_aoc.c` against the generated header and the
+//! `cdylib` with the system C compiler (`$CC`, or `cc`) and runs it.
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// `target/<profile>`, where cargo puts the cdylib built for this test.
+fn profile_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    // target/<profile>/deps/c_api-<hash>
+    exe.parent().unwrap().parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn test_c_program() {
+    let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = profile_dir();
+    let out_dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR"));
+    let program = out_dir.join("test_aoc");
+
+    let compiler = env::var("CC").unwrap_or_else(|_| "cc".to_string());
+    let status = Command::new(&compiler)
+        .args(["-std=c99", "-Wall", "-Wextra", "-Werror"])
+        .arg("-I")
+        .arg(crate_dir.join("include"))
+        .arg(crate_dir.join("tests/c/test_aoc.c"))
+        .arg("-o")
+        .arg(&program)
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-laoc_2023_capi")
+        .status()
+        .unwrap_or_else(|e| panic!("failed to run {}: {}", compiler, e));
+    assert!(status.success(), "compiling test_aoc.c failed");
+
+    let output = Command::new(&program)
+        .arg(crate_dir.join("../examples"))
+        .output()
+        .expect("run test_aoc");
+    assert!(
+        output.status.success(),
+        "test_aoc failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
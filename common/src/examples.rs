@@ -0,0 +1,283 @@
+//! Build script support for the shared example corpus in `examples/`.
+//!
+//! Every day has a directory `examples/dayNN/` holding example inputs
+//! (`NAME.in`) and their expected answers (`NAME.answers`). An answers file
+//! has one line per part:
+//!
+//! ```text
+//! # comments and blank lines are ignored
+//! part_one: 374
+//! part_two(10): 1030
+//! part_two: skip not implemented yet
+//! ```
+//!
+//! A value in parentheses is passed as an extra argument to the solver, and
+//! `skip` (with an optional reason) records that a part is deliberately not
+//! checked. A day's `build.rs` calls [`generate_tests`], which writes one
+//! `#[test]` per example and part to `$OUT_DIR/examples.rs` with the input
+//! embedded through `include_str!`. Skipped parts, and parts an example has
+//! no answer for, become `#[ignore]`d tests so they show up in the test
+//! output instead of silently disappearing.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const PARTS: [&str; 2] = ["part_one", "part_two"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Answer { arg: Option<String>, value: String },
+    Skip { reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: PathBuf,
+    pub parts: Vec<(String, Expected)>,
+}
+
+/// Parse the contents of an answers file into `(part, expected)` pairs.
+pub fn parse_answers(contents: &str) -> Result<Vec<(String, Expected)>, String> {
+    let mut parts = vec![];
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once(':')
+            .ok_or_else(|| format!("line {}: expected `part: answer`", i + 1))?;
+        let (part, arg) = match key.trim().split_once('(') {
+            Some((part, arg)) => {
+                let arg = arg
+                    .strip_suffix(')')
+                    .ok_or_else(|| format!("line {}: unclosed argument", i + 1))?;
+                (part, Some(arg.trim().to_string()))
+            }
+            None => (key.trim(), None),
+        };
+        if !PARTS.contains(&part) {
+            return Err(format!("line {}: unknown part `{}`", i + 1, part));
+        }
+
+        let value = value.trim();
+        let expected = match value.strip_prefix("skip") {
+            Some(reason) => Expected::Skip {
+                reason: reason.trim().to_string(),
+            },
+            None if value.is_empty() => {
+                return Err(format!("line {}: missing answer", i + 1));
+            }
+            None => Expected::Answer {
+                arg,
+                value: value.to_string(),
+            },
+        };
+        parts.push((part.to_string(), expected));
+    }
+    Ok(parts)
+}
+
+/// All examples for `day` in `corpus`, sorted by name.
+pub fn load_examples(corpus: &Path, day: &str) -> Result<Vec<Example>, String> {
+    let dir = corpus.join(day);
+    let entries = fs::read_dir(&dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+
+    let mut examples = vec![];
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_none_or(|ext| ext != "in") {
+            continue;
+        }
+        let name = path.file_stem().unwrap().to_string_lossy().to_string();
+        let answers_path = path.with_extension("answers");
+        let parts = match fs::read_to_string(&answers_path) {
+            Ok(contents) => parse_answers(&contents)
+                .map_err(|e| format!("{}: {}", answers_path.display(), e))?,
+            Err(_) => vec![],
+        };
+        examples.push(Example {
+            name,
+            input: path,
+            parts,
+        });
+    }
+    examples.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(examples)
+}
+
+/// Rust source with one test function per example and part. The tests call
+/// `solve_part_one` / `solve_part_two`, which must be in scope where the
+/// source is included.
+pub fn render_tests(examples: &[Example]) -> String {
+    let mut out = String::new();
+    for example in examples {
+        let name = identifier(&example.name);
+        let input = fs::canonicalize(&example.input).unwrap_or(example.input.clone());
+
+        for part in PARTS {
+            let expectations: Vec<&Expected> = example
+                .parts
+                .iter()
+                .filter(|(p, _)| p == part)
+                .map(|(_, e)| e)
+                .collect();
+
+            if expectations.is_empty() {
+                let reason = format!("{}: no expected answer for {}", example.name, part);
+                write_ignored(&mut out, &format!("{}_{}", name, part), &reason);
+            }
+
+            for expected in expectations {
+                match expected {
+                    Expected::Skip { reason } => {
+                        let reason = if reason.is_empty() {
+                            format!("{}: {} skipped", example.name, part)
+                        } else {
+                            format!("{}: {} skipped, {}", example.name, part, reason)
+                        };
+                        write_ignored(&mut out, &format!("{}_{}", name, part), &reason);
+                    }
+                    Expected::Answer { arg, value } => {
+                        let (test_name, call) = match arg {
+                            Some(arg) => (
+                                format!("{}_{}_{}", name, part, identifier(arg)),
+                                format!("solve_{}(INPUT, {})", part, arg),
+                            ),
+                            None => (
+                                format!("{}_{}", name, part),
+                                format!("solve_{}(INPUT)", part),
+                            ),
+                        };
+                        writeln!(out, "#[test]").unwrap();
+                        writeln!(out, "fn {}() {{", test_name).unwrap();
+                        writeln!(
+                            out,
+                            "    const INPUT: &str = include_str!({:?});",
+                            input.display().to_string()
+                        )
+                        .unwrap();
+                        writeln!(out, "    assert_eq!({}.to_string(), {:?});", call, value)
+                            .unwrap();
+                        writeln!(out, "}}\n").unwrap();
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+fn write_ignored(out: &mut String, test_name: &str, reason: &str) {
+    writeln!(out, "#[test]").unwrap();
+    writeln!(out, "#[ignore = {:?}]", reason).unwrap();
+    writeln!(out, "fn {}() {{}}\n", test_name).unwrap();
+}
+
+fn identifier(name: &str) -> String {
+    let ident: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if ident.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("example_{}", ident)
+    } else {
+        ident
+    }
+}
+
+/// Entry point for a day's `build.rs`: generate `$OUT_DIR/examples.rs` from
+/// `../examples/<day>/` relative to the crate being built.
+pub fn generate_tests(day: &str) {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("called from a build script");
+    let out_dir = std::env::var("OUT_DIR").expect("called from a build script");
+    let corpus = Path::new(&manifest_dir).join("../examples");
+
+    println!("cargo:rerun-if-changed={}", corpus.join(day).display());
+    let examples = load_examples(&corpus, day).unwrap_or_else(|e| panic!("{}", e));
+    for example in &examples {
+        println!("cargo:rerun-if-changed={}", example.input.display());
+        println!(
+            "cargo:rerun-if-changed={}",
+            example.input.with_extension("answers").display()
+        );
+    }
+
+    fs::write(
+        Path::new(&out_dir).join("examples.rs"),
+        render_tests(&examples),
+    )
+    .expect("write generated example tests");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        let answers = "# day 11\npart_one: 374\n\npart_two(10): 1030\npart_two: skip too slow\n";
+        assert_eq!(
+            parse_answers(answers),
+            Ok(vec![
+                (
+                    "part_one".to_string(),
+                    Expected::Answer {
+                        arg: None,
+                        value: "374".to_string()
+                    }
+                ),
+                (
+                    "part_two".to_string(),
+                    Expected::Answer {
+                        arg: Some("10".to_string()),
+                        value: "1030".to_string()
+                    }
+                ),
+                (
+                    "part_two".to_string(),
+                    Expected::Skip {
+                        reason: "too slow".to_string()
+                    }
+                ),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_answers_errors() {
+        assert!(parse_answers("part_three: 1").is_err());
+        assert!(parse_answers("part_one 1").is_err());
+        assert!(parse_answers("part_one:").is_err());
+        assert!(parse_answers("part_two(10: 1").is_err());
+    }
+
+    #[test]
+    fn test_render_tests() {
+        let example = Example {
+            name: "example-2".to_string(),
+            input: PathBuf::from("/corpus/day01/example-2.in"),
+            parts: vec![(
+                "part_two".to_string(),
+                Expected::Answer {
+                    arg: None,
+                    value: "281".to_string(),
+                },
+            )],
+        };
+        let rendered = render_tests(&[example]);
+        assert!(rendered.contains(
+            "#[ignore = \"example-2: no expected answer for part_one\"]\nfn example_2_part_one() {}"
+        ));
+        assert!(rendered.contains("fn example_2_part_two() {"));
+        assert!(rendered.contains("assert_eq!(solve_part_two(INPUT).to_string(), \"281\");"));
+    }
+}
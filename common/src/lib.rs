@@ -0,0 +1,3 @@
+pub mod examples;
+pub mod math;
+pub mod parse;
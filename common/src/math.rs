@@ -0,0 +1,260 @@
+//! Number theory helpers shared between days.
+//!
+//! Everything here is overflow aware: operations that can exceed their integer
+//! type return `Option` instead of wrapping or panicking.
+
+/// Greatest common divisor. `gcd(0, n)` is `n`, so zero is a valid input.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or `None` if it does not fit in a `u64`.
+///
+/// `lcm(0, n)` is `0`.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Least common multiple of every value, `1` for an empty iterator.
+pub fn lcm_all<I: IntoIterator<Item = u64>>(values: I) -> Option<u64> {
+    values.into_iter().try_fold(1, lcm)
+}
+
+/// Extended Euclid, returns `(g, x, y)` such that `a * x + b * y == g` where
+/// `g` is the non-negative gcd of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Multiplicative inverse of `a` modulo `m`, if `a` and `m` are coprime.
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd((a % m) as i128, m as i128);
+    if g != 1 {
+        return None;
+    }
+    Some(x.rem_euclid(m as i128) as u64)
+}
+
+/// Chinese remainder theorem for `(residue, modulus)` congruences.
+///
+/// Moduli do not need to be coprime. Returns the smallest non-negative
+/// solution together with the combined modulus (the lcm of all moduli), or
+/// `None` if the congruences contradict each other, a modulus is zero, or the
+/// combined modulus does not fit in a `u64`.
+///
+/// This is what lines up cycles that start at an offset: something that is
+/// first seen at step `offset` and then every `cycle` steps is the congruence
+/// `(offset % cycle, cycle)`.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let mut acc: (u64, u64) = (0, 1);
+    for &(residue, modulus) in congruences {
+        if modulus == 0 {
+            return None;
+        }
+        acc = crt_pair(acc, (residue % modulus, modulus))?;
+    }
+    Some(acc)
+}
+
+fn crt_pair((a1, m1): (u64, u64), (a2, m2): (u64, u64)) -> Option<(u64, u64)> {
+    let g = gcd(m1, m2);
+    let diff = (a2 as i128 - a1 as i128).rem_euclid(m2 as i128) as u64;
+    if !diff.is_multiple_of(g) {
+        return None;
+    }
+    let m2_g = m2 / g;
+    let combined = (m1 / g).checked_mul(m2)?;
+    // solve m1 * k == diff (mod m2) for k, working modulo m2 / g
+    let k = match m2_g {
+        1 => 0,
+        _ => {
+            let inv = mod_inverse((m1 / g) % m2_g, m2_g)?;
+            ((diff / g) as u128 * inv as u128 % m2_g as u128) as u64
+        }
+    };
+    // m1 * k < combined, so this cannot overflow
+    let x = (a1 as u128 + m1 as u128 * k as u128) % combined as u128;
+    Some((x as u64, combined))
+}
+
+/// Floor of the square root of `n`.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // start from a power of two that is guaranteed to be >= sqrt(n) and let
+    // Newton's method walk down to the floor
+    let bits = 128 - n.leading_zeros();
+    let mut x = 1u128 << bits.div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// Binomial coefficient `n choose k`, or `None` if it does not fit in a `u64`.
+pub fn binomial(n: u64, k: u64) -> Option<u64> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        // result is C(n, i) here, so the division is always exact
+        result = result * (n - i) as u128 / (i + 1) as u128;
+        if result > u64::MAX as u128 {
+            return None;
+        }
+    }
+    Some(result as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_gcd_with_zero() {
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(0, 12), 12);
+        assert_eq!(gcd(12, 0), 12);
+    }
+
+    #[test]
+    fn test_lcm_overflow() {
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(lcm_all([]), Some(1));
+        assert_eq!(lcm_all([2, 3, 4]), Some(12));
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // non-coprime but consistent
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        // non-coprime and inconsistent
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        assert_eq!(crt(&[(1, 0)]), None);
+        assert_eq!(crt(&[]), Some((0, 1)));
+    }
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn test_binomial() {
+        assert_eq!(binomial(5, 2), Some(10));
+        assert_eq!(binomial(5, 6), Some(0));
+        assert_eq!(binomial(67, 33), Some(14226520737620288370));
+        assert_eq!(binomial(68, 34), None);
+    }
+
+    proptest! {
+        #[test]
+        fn prop_gcd_divides_both(a in 1u64.., b in 1u64..) {
+            let g = gcd(a, b);
+            prop_assert_eq!(a % g, 0);
+            prop_assert_eq!(b % g, 0);
+            prop_assert_eq!(gcd(a / g, b / g), 1);
+        }
+
+        #[test]
+        fn prop_lcm_is_common_multiple(a in 1u64..1_000_000, b in 1u64..1_000_000) {
+            let l = lcm(a, b).unwrap();
+            prop_assert_eq!(l % a, 0);
+            prop_assert_eq!(l % b, 0);
+            prop_assert_eq!(l as u128 * gcd(a, b) as u128, a as u128 * b as u128);
+        }
+
+        #[test]
+        fn prop_lcm_never_wraps(a in any::<u64>(), b in any::<u64>()) {
+            let expected = if a == 0 || b == 0 {
+                Some(0)
+            } else {
+                u64::try_from(a as u128 / gcd(a, b) as u128 * b as u128).ok()
+            };
+            prop_assert_eq!(lcm(a, b), expected);
+        }
+
+        #[test]
+        fn prop_extended_gcd_identity(a in any::<i64>(), b in any::<i64>()) {
+            let (a, b) = (a as i128, b as i128);
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert!(g >= 0);
+            prop_assert_eq!(a * x + b * y, g);
+            prop_assert_eq!(g as u64, gcd(a.unsigned_abs() as u64, b.unsigned_abs() as u64));
+        }
+
+        #[test]
+        fn prop_crt_satisfies_congruences(
+            congruences in prop::collection::vec((any::<u64>(), 1u64..10_000), 0..5)
+        ) {
+            match crt(&congruences) {
+                Some((x, m)) => {
+                    prop_assert!(x < m);
+                    for (r, n) in &congruences {
+                        prop_assert_eq!(x % n, r % n);
+                    }
+                }
+                None => {
+                    // either the lcm overflowed or there is a pair with no solution
+                    let overflow = lcm_all(congruences.iter().map(|c| c.1)).is_none();
+                    let contradiction = congruences.iter().any(|(r1, n1)| {
+                        congruences.iter().any(|(r2, n2)| {
+                            (r1 % n1) % gcd(*n1, *n2) != (r2 % n2) % gcd(*n1, *n2)
+                        })
+                    });
+                    prop_assert!(overflow || contradiction);
+                }
+            }
+        }
+
+        #[test]
+        fn prop_isqrt_is_floor(n in any::<u128>()) {
+            let r = isqrt(n);
+            prop_assert!(r * r <= n);
+            prop_assert!((r + 1).checked_mul(r + 1).is_none_or(|sq| sq > n));
+        }
+
+        #[test]
+        fn prop_binomial_pascal(n in 1u64..60, k in 1u64..60) {
+            prop_assert_eq!(
+                binomial(n, k).unwrap(),
+                binomial(n - 1, k - 1).unwrap() + binomial(n - 1, k).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,328 @@
+//! Small parsing layer on top of `nom` for the shapes that keep showing up in
+//! puzzle inputs: labelled headers (`Time: 7 15 30`), whitespace separated
+//! integers, `key = (a, b)` tuples and blank line separated sections.
+//!
+//! Parsers work on `&str` and use `VerboseError` so that a failure can be
+//! turned into a [`ParseError`] pointing at the line and column of the input
+//! that did not match.
+
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::{alphanumeric1, char, digit1, multispace0, space0, space1};
+use nom::combinator::{all_consuming, map_res, opt, recognize};
+use nom::error::{context, ErrorKind, VerboseError, VerboseErrorKind};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
+use nom::{Finish, IResult, Parser};
+use std::fmt;
+use std::str::FromStr;
+
+pub type PResult<'a, O> = IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// Location and description of the first thing in an input that did not
+/// match the expected format. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Locate a `nom` error inside `source`. The error must point into
+    /// `source`, which is the case for any parser that was given `source` or
+    /// a slice of it.
+    pub fn from_verbose(source: &str, err: VerboseError<&str>) -> ParseError {
+        let mut errors = err.errors.iter().peekable();
+        let (at, message) = match errors.next() {
+            // a bare nom failure directly under a context reads better as
+            // "expected <context>"
+            Some((at, VerboseErrorKind::Nom(_))) => match errors.peek() {
+                Some((ctx_at, VerboseErrorKind::Context(c))) if ctx_at.as_ptr() == at.as_ptr() => {
+                    errors.next();
+                    (*at, format!("expected {}", c))
+                }
+                _ => (*at, describe(&err.errors[0].1)),
+            },
+            Some((at, kind)) => (*at, describe(kind)),
+            None => (source, "invalid input".to_string()),
+        };
+        let contexts: Vec<&str> = errors
+            .filter_map(|(_, kind)| match kind {
+                VerboseErrorKind::Context(c) => Some(*c),
+                _ => None,
+            })
+            .collect();
+        let message = if contexts.is_empty() {
+            message
+        } else {
+            format!("{} in {}", message, contexts.join(" in "))
+        };
+
+        ParseError::at(source, at, message)
+    }
+
+    /// An error at the start of `at`, which should be a slice of `source`,
+    /// for hand-written parsers that do not go through `nom`.
+    pub fn at(source: &str, at: &str, message: impl Into<String>) -> ParseError {
+        let (line, column) = position(source, at);
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn describe(kind: &VerboseErrorKind) -> String {
+    match kind {
+        VerboseErrorKind::Context(c) => format!("expected {}", c),
+        VerboseErrorKind::Char(c) => format!("expected '{}'", c),
+        VerboseErrorKind::Nom(ErrorKind::Eof) => "unexpected trailing input".to_string(),
+        VerboseErrorKind::Nom(k) => format!("unexpected input ({:?})", k),
+    }
+}
+
+/// Line and column of `at` within `source`, falling back to the end of
+/// `source` if `at` is not a slice of it.
+fn position(source: &str, at: &str) -> (usize, usize) {
+    let start = source.as_ptr() as usize;
+    let offset = (at.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|o| *o <= source.len())
+        .unwrap_or(source.len());
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+/// Run `parser` over the whole of `input` (ignoring trailing whitespace),
+/// reporting errors relative to `input`.
+pub fn parse_all<'a, O, P>(input: &'a str, parser: P) -> Result<O, ParseError>
+where
+    P: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    parse_within(input, input, parser)
+}
+
+/// Run `parser` over the whole of `slice`, which must be part of `source`,
+/// reporting errors at their line and column in `source`. This lets a day
+/// parse line by line (or section by section) and still point at the right
+/// place in the full puzzle input.
+pub fn parse_within<'a, O, P>(source: &'a str, slice: &'a str, parser: P) -> Result<O, ParseError>
+where
+    P: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    all_consuming(terminated(parser, multispace0))(slice)
+        .finish()
+        .map(|(_, o)| o)
+        .map_err(|e| ParseError::from_verbose(source, e))
+}
+
+/// An optionally negative decimal integer.
+pub fn integer<T: FromStr>(input: &str) -> PResult<'_, T> {
+    context(
+        "integer",
+        map_res(recognize(pair(opt(char('-')), digit1)), str::parse::<T>),
+    )(input)
+}
+
+/// One or more integers separated by spaces or tabs, with optional leading
+/// whitespace. Does not cross line breaks.
+pub fn integers<T: FromStr>(input: &str) -> PResult<'_, Vec<T>> {
+    preceded(space0, separated_list1(space1, integer::<T>))(input)
+}
+
+/// One or more space separated runs of digits, kept as the original text.
+pub fn digit_groups(input: &str) -> PResult<'_, Vec<&str>> {
+    preceded(space0, separated_list1(space1, digit1))(input)
+}
+
+/// An alphanumeric name such as a node label.
+pub fn word(input: &str) -> PResult<'_, &str> {
+    context("name", alphanumeric1)(input)
+}
+
+/// `label:` followed by `inner`, e.g. `labelled("Time", integers)` for
+/// `Time:      7  15   30`.
+pub fn labelled<'a, O, F>(label: &'static str, inner: F) -> impl FnMut(&'a str) -> PResult<'a, O>
+where
+    F: Parser<&'a str, O, VerboseError<&'a str>>,
+{
+    context(
+        label,
+        preceded(tuple((tag(label), space0, char(':'), space0)), inner),
+    )
+}
+
+/// `label N:` returning `N`, e.g. `numbered("Card")` for `Card  12:`.
+pub fn numbered<'a, T: FromStr>(label: &'static str) -> impl FnMut(&'a str) -> PResult<'a, T> {
+    context(
+        label,
+        delimited(
+            pair(tag(label), space1),
+            integer::<T>,
+            pair(space0, char(':')),
+        ),
+    )
+}
+
+/// `key = (left, right)` with flexible spacing around the punctuation.
+pub fn key_tuple<'a, K, L, R, FK, FL, FR>(
+    key: FK,
+    left: FL,
+    right: FR,
+) -> impl FnMut(&'a str) -> PResult<'a, (K, (L, R))>
+where
+    FK: Parser<&'a str, K, VerboseError<&'a str>>,
+    FL: Parser<&'a str, L, VerboseError<&'a str>>,
+    FR: Parser<&'a str, R, VerboseError<&'a str>>,
+{
+    let equals = tuple((space0, char('='), space0));
+    let tuple_body = delimited(
+        pair(char('('), space0),
+        separated_pair(left, tuple((space0, char(','), space0)), right),
+        pair(space0, char(')')),
+    );
+    context("key = (a, b)", separated_pair(key, equals, tuple_body))
+}
+
+/// A single line break, `\n` or `\r\n`.
+pub fn line_ending(input: &str) -> PResult<'_, &str> {
+    alt((tag("\n"), tag("\r\n")))(input)
+}
+
+/// Split `input` into blank line separated sections. Each section is a slice
+/// of `input` without its surrounding blank lines, so it can be handed to
+/// [`parse_within`].
+pub fn sections(input: &str) -> Sections<'_> {
+    Sections { rest: input }
+}
+
+pub struct Sections<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        // skip the blank lines before the section
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+            let line_end = self
+                .rest
+                .find('\n')
+                .map(|i| i + 1)
+                .unwrap_or(self.rest.len());
+            if !self.rest[..line_end].trim().is_empty() {
+                break;
+            }
+            self.rest = &self.rest[line_end..];
+        }
+
+        // a section runs until the next line that is empty or only whitespace
+        let mut end = 0;
+        for line in self.rest.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                break;
+            }
+            end += line.len();
+        }
+        let section = &self.rest[..end];
+        self.rest = &self.rest[end..];
+        Some(section.trim_end_matches(['\n', '\r']))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(
+            integers::<i64>("  7  15 -30\nrest"),
+            Ok(("\nrest", vec![7, 15, -30]))
+        );
+        assert!(integers::<u32>("x").is_err());
+    }
+
+    #[test]
+    fn test_labelled() {
+        let input = "Time:      7  15   30";
+        assert_eq!(
+            parse_all(input, labelled("Time", integers::<u32>)),
+            Ok(vec![7, 15, 30])
+        );
+    }
+
+    #[test]
+    fn test_numbered() {
+        assert_eq!(numbered::<u32>("Card")("Card  12: 1 2"), Ok((" 1 2", 12)));
+    }
+
+    #[test]
+    fn test_key_tuple() {
+        assert_eq!(
+            parse_all("AAA = (BBB, CCC)", key_tuple(word, word, word)),
+            Ok(("AAA", ("BBB", "CCC")))
+        );
+        assert_eq!(
+            parse_all("x=(1,2)", key_tuple(word, integer::<u8>, integer::<u8>)),
+            Ok(("x", (1, 2)))
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "RL\n\nAAA\nBBB\n\n\n  \nCCC\n";
+        assert_eq!(
+            sections(input).collect::<Vec<_>>(),
+            ["RL", "AAA\nBBB", "CCC"]
+        );
+        let input = "a\r\n\r\nb";
+        assert_eq!(sections(input).collect::<Vec<_>>(), ["a", "b"]);
+        assert_eq!(sections("\n\n").count(), 0);
+    }
+
+    #[test]
+    fn test_error_location() {
+        let input = "Time: 1 2\nDistance: 3 x";
+        let (_, second) = input.split_once('\n').unwrap();
+        let err = parse_within(input, second, labelled("Distance", integers::<u32>)).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 13));
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 13: unexpected trailing input"
+        );
+
+        let err = parse_all("Tme: 1", labelled("Time", integers::<u32>)).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+        assert_eq!(err.message, "expected Time");
+
+        let err = parse_all("Time: x", labelled("Time", integers::<u32>)).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 7));
+        assert_eq!(err.message, "expected integer in Time");
+
+        let input = "ab\ncdé f";
+        let err = ParseError::at(input, &input[8..], "expected g");
+        assert_eq!(err.to_string(), "line 2, column 5: expected g");
+    }
+}
@@ -1,4 +1,5 @@
 use aoc_2023::day01::*;
+use aoc_2023::day01_bytes::*;
 
 fn main() {
     // Run registered benchmarks.
@@ -11,7 +12,17 @@ fn day_one_part_one() {
     solve_part_one(divan::black_box(include_str!("../input/day01.in")));
 }
 
+#[divan::bench]
+fn day_one_part_one_with_bytes() {
+    solve_part_one_with_bytes(divan::black_box(include_str!("../input/day01.in")));
+}
+
 #[divan::bench]
 fn day_one_part_two() {
     solve_part_two(divan::black_box(include_str!("../input/day01.in")));
 }
+
+#[divan::bench]
+fn day_one_part_two_with_bytes() {
+    solve_part_two_with_bytes(divan::black_box(include_str!("../input/day01.in")));
+}
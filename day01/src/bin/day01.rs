@@ -1,9 +1,41 @@
 use aoc_2023::day01::*;
+use aoc_2023::report::calibration_report;
+use std::io::IsTerminal;
 
 fn main() {
+    // `day01 --report [FILE]` prints how every line was read instead
+    let mut args = std::env::args().skip(1);
+    if args.next().as_deref() == Some("--report") {
+        let input = match args.next() {
+            Some(path) => std::fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("could not read {}: {}", path, e)),
+            None => include_str!("../../input/day01.in").to_string(),
+        };
+        print_report(&input);
+        return;
+    }
+
     let p1_answer = solve_part_one(include_str!("../../input/day01.in"));
     println!("p1: {}", p1_answer);
 
     let p2_answer = solve_part_two(include_str!("../../input/day01.in"));
     println!("p2: {}", p2_answer);
 }
+
+fn print_report(input: &str) {
+    let color = std::io::stdout().is_terminal();
+    let report = calibration_report(input);
+    for line in &report {
+        match (line.disagrees(), color) {
+            (true, true) => println!("\x1b[1;33m! {}\x1b[0m", line),
+            (true, false) => println!("! {}", line),
+            (false, _) => println!("  {}", line),
+        }
+    }
+    let disagreements = report.iter().filter(|line| line.disagrees()).count();
+    println!(
+        "{} lines, parts one and two disagree on {}",
+        report.len(),
+        disagreements
+    );
+}
@@ -1,14 +1,55 @@
+use crate::vocabulary::DigitMatcher;
 use rayon::prelude::*;
+use std::fmt;
+use std::sync::LazyLock;
+
+/// A line without any digit, numbered from 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoDigitError {
+    pub line: usize,
+}
+
+impl fmt::Display for NoDigitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {} does not contain a digit", self.line)
+    }
+}
+
+impl std::error::Error for NoDigitError {}
 
 pub fn solve_part_one(input: &str) -> u32 {
-    input.par_lines().map(calibration_value).sum()
+    try_solve_part_one(input).unwrap_or_else(|e| panic!("{}", e))
 }
 
 pub fn solve_part_two(input: &str) -> u32 {
-    input.par_lines().map(calibration_value_detect_str).sum()
+    try_solve_part_two(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn try_solve_part_one(input: &str) -> Result<u32, NoDigitError> {
+    sum_lines(input, calibration_value)
+}
+
+pub fn try_solve_part_two(input: &str) -> Result<u32, NoDigitError> {
+    sum_lines(input, calibration_value_detect_str)
 }
 
-fn calibration_value(line: &str) -> u32 {
+fn sum_lines(input: &str, value: fn(&str) -> Option<u32>) -> Result<u32, NoDigitError> {
+    input
+        .par_lines()
+        .map(value)
+        .sum::<Option<u32>>()
+        .ok_or_else(|| {
+            // only pay for numbering the lines when one of them is bad
+            let i = input.lines().position(|line| value(line).is_none());
+            NoDigitError {
+                line: i.expect("a line without a digit") + 1,
+            }
+        })
+}
+
+/// First and last digit of `line` as a two digit number, `None` if the line
+/// has no digits.
+pub fn calibration_value(line: &str) -> Option<u32> {
     let mut answer = "".to_string();
     for c in line.chars() {
         if c.is_ascii_digit() {
@@ -22,123 +63,118 @@ fn calibration_value(line: &str) -> u32 {
             break;
         }
     }
-    answer.parse::<u32>().unwrap()
+    answer.parse::<u32>().ok()
 }
 
-fn calibration_value_detect_str(line: &str) -> u32 {
-    let mut answer = "".to_string();
-    let chars: Vec<char> = line.chars().collect();
-    for (i, &c) in chars.iter().enumerate() {
-        if c.is_ascii_digit() {
-            answer.push(c);
-            break;
-        }
-        let digit_at = at_digit(i, &c, line);
-        match digit_at {
-            Some(digit) => {
-                answer.push(digit);
-                break;
-            }
-            None => continue,
-        }
-    }
-    for (i, &c) in chars.iter().enumerate().rev() {
-        if c.is_ascii_digit() {
-            answer.push(c);
-            break;
-        }
-        let digit_at = at_digit_rev(i, &c, line);
-        match digit_at {
-            Some(digit) => {
-                answer.push(digit);
-                break;
-            }
-            None => continue,
-        }
-    }
-    answer.parse::<u32>().unwrap()
+/// The vocabulary of part two, built once and shared by every thread.
+static ENGLISH_MATCHER: LazyLock<DigitMatcher> = LazyLock::new(DigitMatcher::english);
+
+/// Like [`calibration_value`] but also counting the spelled out digits `one`
+/// to `nine`, which may overlap (`eightwo` is 82).
+pub fn calibration_value_detect_str(line: &str) -> Option<u32> {
+    let first = ENGLISH_MATCHER.first(line)?.value;
+    let last = ENGLISH_MATCHER.last(line)?.value;
+    Some(first * 10 + last)
 }
 
-fn at_digit(i: usize, c: &char, line: &str) -> Option<char> {
-    let possible_digits = starts_with(c);
-    if !possible_digits.is_empty() {
-        for possible_digit in possible_digits {
-            let end_idx = i + possible_digit.len();
-            if end_idx < line.len() && possible_digit == &line[i..end_idx] {
-                return what_digit(possible_digit);
-            }
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vocabulary::ENGLISH;
+    use proptest::prelude::*;
+
+    #[test]
+    fn examples_with_str() {
+        assert_eq!(calibration_value_detect_str("two1nine"), Some(29));
+        assert_eq!(calibration_value_detect_str("eighttwothree"), Some(83));
+        assert_eq!(calibration_value_detect_str("abcone2threexyz"), Some(13));
+        assert_eq!(calibration_value_detect_str("xtwoone3four"), Some(24));
+        assert_eq!(calibration_value_detect_str("4nineeightseven2"), Some(42));
+        assert_eq!(calibration_value_detect_str("zoneight234"), Some(14));
+        assert_eq!(calibration_value_detect_str("7pqrstsixteen"), Some(76));
     }
-    None
-}
 
-fn at_digit_rev(i: usize, c: &char, line: &str) -> Option<char> {
-    let possible_digits = ends_with(c);
-    if !possible_digits.is_empty() {
-        for possible_digit in possible_digits {
-            if possible_digit.len() > (i + 1) {
-                continue;
-            }
-            let start_idx = (i + 1) - possible_digit.len();
-            if possible_digit == &line[start_idx..(i + 1)] {
-                return what_digit(possible_digit);
-            }
+    #[test]
+    fn test_words_at_line_end() {
+        // the only word ends the line, so it is both the first and last digit
+        for (word, value) in ENGLISH {
+            assert_eq!(
+                calibration_value_detect_str(&format!("abc{}", word)),
+                Some(value * 11)
+            );
+            assert_eq!(calibration_value_detect_str(word), Some(value * 11));
+            assert_eq!(
+                calibration_value_detect_str(&format!("{}xyz", word)),
+                Some(value * 11)
+            );
         }
+        assert_eq!(calibration_value_detect_str("1abcnine"), Some(19));
+        assert_eq!(calibration_value_detect_str("abcnine1"), Some(91));
+        // a cut off word at the end is not a digit
+        assert_eq!(calibration_value_detect_str("4nin"), Some(44));
     }
-    None
-}
 
-fn what_digit(name: &str) -> Option<char> {
-    match name {
-        "one" => Some('1'),
-        "two" => Some('2'),
-        "three" => Some('3'),
-        "four" => Some('4'),
-        "five" => Some('5'),
-        "six" => Some('6'),
-        "seven" => Some('7'),
-        "eight" => Some('8'),
-        "nine" => Some('9'),
-        _ => None,
+    #[test]
+    fn test_overlapping_words() {
+        assert_eq!(calibration_value_detect_str("eightwo"), Some(82));
+        assert_eq!(calibration_value_detect_str("oneight"), Some(18));
+        assert_eq!(calibration_value_detect_str("twone"), Some(21));
+        assert_eq!(calibration_value_detect_str("sevenine"), Some(79));
+        assert_eq!(calibration_value_detect_str("threeightwone"), Some(31));
+        assert_eq!(calibration_value_detect_str("nineight"), Some(98));
     }
-}
 
-fn starts_with(c: &char) -> Vec<&'static str> {
-    match c {
-        'o' => vec!["one"],
-        't' => vec!["two", "three"],
-        'f' => vec!["four", "five"],
-        's' => vec!["six", "seven"],
-        'e' => vec!["eight"],
-        'n' => vec!["nine"],
-        _ => vec![],
-    }
-}
+    #[test]
+    fn test_no_digits() {
+        assert_eq!(calibration_value(""), None);
+        assert_eq!(calibration_value("abcnine"), None);
+        assert_eq!(calibration_value_detect_str(""), None);
+        assert_eq!(calibration_value_detect_str("xyz"), None);
+        assert_eq!(calibration_value_detect_str("nin ine"), None);
 
-fn ends_with(c: &char) -> Vec<&'static str> {
-    match c {
-        'e' => vec!["one", "three", "five", "nine"],
-        'o' => vec!["two"],
-        'r' => vec!["four"],
-        'x' => vec!["six"],
-        'n' => vec!["seven"],
-        't' => vec!["eight"],
-        _ => vec![],
+        assert_eq!(try_solve_part_one("a1\nb2\n"), Ok(33));
+        assert_eq!(
+            try_solve_part_one("a1\nnine\nb2\n"),
+            Err(NoDigitError { line: 2 })
+        );
+        assert_eq!(
+            try_solve_part_two("a1\nnine\nb\n"),
+            Err(NoDigitError { line: 3 })
+        );
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn examples_with_str() {
-        assert_eq!(calibration_value_detect_str("two1nine"), 29);
-        assert_eq!(calibration_value_detect_str("eighttwothree"), 83);
-        assert_eq!(calibration_value_detect_str("abcone2threexyz"), 13);
-        assert_eq!(calibration_value_detect_str("xtwoone3four"), 24);
-        assert_eq!(calibration_value_detect_str("4nineeightseven2"), 42);
-        assert_eq!(calibration_value_detect_str("zoneight234"), 14);
-        assert_eq!(calibration_value_detect_str("7pqrstsixteen"), 76);
+    fn test_unicode() {
+        assert_eq!(calibration_value("é1ü2ß"), Some(12));
+        assert_eq!(calibration_value_detect_str("éone"), Some(11));
+        assert_eq!(calibration_value_detect_str("ßtwoéüthreeß"), Some(23));
+        assert_eq!(calibration_value_detect_str("日本eight語"), Some(88));
+        assert_eq!(calibration_value_detect_str("🎄six🎄7🎄"), Some(67));
+        assert_eq!(calibration_value_detect_str("ñïñé"), None);
+    }
+
+    proptest! {
+        // lines glued together from words, pieces of words, digits and
+        // multi-byte characters, checked against the byte version
+        #[test]
+        fn prop_matches_vocabulary(
+            pieces in prop::collection::vec(
+                prop::sample::select(vec![
+                    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+                    "on", "ne", "tw", "thr", "ight", "nin", "ev", "x", "é", "日", "7", "0",
+                ]),
+                0..12,
+            )
+        ) {
+            let line = pieces.concat();
+            prop_assert_eq!(
+                calibration_value_detect_str(&line),
+                crate::day01_bytes::calibration_value_detect_str(line.as_bytes())
+            );
+            prop_assert_eq!(
+                calibration_value(&line).map(u64::from),
+                DigitMatcher::numerals().calibration_value(&line)
+            );
+        }
     }
 }
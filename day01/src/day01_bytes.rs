@@ -0,0 +1,192 @@
+use crate::day01::NoDigitError;
+use crate::vocabulary::ENGLISH;
+use rayon::prelude::*;
+
+/// Bytes compared at once when searching for a digit. Checking a whole block
+/// without an early exit lets the compiler turn the comparison into vector
+/// instructions, the first/last match is then picked out of the bit mask.
+const BLOCK: usize = 16;
+
+pub fn solve_part_one_with_bytes(input: &str) -> u32 {
+    try_solve_part_one_with_bytes(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+pub fn solve_part_two_with_bytes(input: &str) -> u32 {
+    try_solve_part_two_with_bytes(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Part one, reporting the same [`NoDigitError`] as
+/// [`crate::day01::try_solve_part_one`], blank lines included.
+pub fn try_solve_part_one_with_bytes(input: &str) -> Result<u32, NoDigitError> {
+    sum_lines(input, calibration_value)
+}
+
+pub fn try_solve_part_two_with_bytes(input: &str) -> Result<u32, NoDigitError> {
+    sum_lines(input, calibration_value_detect_str)
+}
+
+fn sum_lines(input: &str, value: fn(&[u8]) -> Option<u32>) -> Result<u32, NoDigitError> {
+    // split like `str::lines` so that line numbers agree with the `String`
+    // version
+    input
+        .par_lines()
+        .map(|line| value(line.as_bytes()))
+        .sum::<Option<u32>>()
+        .ok_or_else(|| {
+            let i = input
+                .lines()
+                .position(|line| value(line.as_bytes()).is_none());
+            NoDigitError {
+                line: i.expect("a line without a digit") + 1,
+            }
+        })
+}
+
+fn is_digit(b: u8) -> bool {
+    b.wrapping_sub(b'0') < 10
+}
+
+/// Bit `i` is set if `block[i]` is an ASCII digit.
+fn digit_mask(block: &[u8]) -> u32 {
+    block
+        .iter()
+        .enumerate()
+        .fold(0, |mask, (i, &b)| mask | ((is_digit(b) as u32) << i))
+}
+
+fn first_digit(line: &[u8]) -> Option<usize> {
+    let mut blocks = line.chunks_exact(BLOCK);
+    for (n, block) in blocks.by_ref().enumerate() {
+        let mask = digit_mask(block);
+        if mask != 0 {
+            return Some(n * BLOCK + mask.trailing_zeros() as usize);
+        }
+    }
+    let rest_start = line.len() - blocks.remainder().len();
+    let mask = digit_mask(blocks.remainder());
+    (mask != 0).then(|| rest_start + mask.trailing_zeros() as usize)
+}
+
+fn last_digit(line: &[u8]) -> Option<usize> {
+    let mut blocks = line.rchunks_exact(BLOCK);
+    for (n, block) in blocks.by_ref().enumerate() {
+        let mask = digit_mask(block);
+        if mask != 0 {
+            let start = line.len() - (n + 1) * BLOCK;
+            return Some(start + 31 - mask.leading_zeros() as usize);
+        }
+    }
+    let mask = digit_mask(blocks.remainder());
+    (mask != 0).then(|| 31 - mask.leading_zeros() as usize)
+}
+
+/// First and last digit of `line` as a two digit number, `None` if the line
+/// has no digits.
+pub fn calibration_value(line: &[u8]) -> Option<u32> {
+    let first = line[first_digit(line)?] - b'0';
+    let last = line[last_digit(line)?] - b'0';
+    Some((first * 10 + last) as u32)
+}
+
+/// Like [`calibration_value`] but also counting the words of
+/// [`ENGLISH`], which may overlap (`eightwo` is 82).
+pub fn calibration_value_detect_str(line: &[u8]) -> Option<u32> {
+    let first = (0..line.len()).find_map(|i| digit_starting_at(&line[i..]))?;
+    let last = (1..=line.len())
+        .rev()
+        .find_map(|i| digit_ending_at(&line[..i]))?;
+    Some(first * 10 + last)
+}
+
+/// Value of the digit or spelled out digit at the start of `rest`.
+fn digit_starting_at(rest: &[u8]) -> Option<u32> {
+    let &b = rest.first()?;
+    if is_digit(b) {
+        return Some(u32::from(b - b'0'));
+    }
+    ENGLISH
+        .iter()
+        .find(|(word, _)| rest.starts_with(word.as_bytes()))
+        .map(|&(_, value)| value)
+}
+
+/// Value of the digit or spelled out digit at the end of `head`.
+fn digit_ending_at(head: &[u8]) -> Option<u32> {
+    let &b = head.last()?;
+    if is_digit(b) {
+        return Some(u32::from(b - b'0'));
+    }
+    ENGLISH
+        .iter()
+        .find(|(word, _)| head.ends_with(word.as_bytes()))
+        .map(|&(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digit_search_across_blocks() {
+        let mut line = vec![b'x'; 40];
+        assert_eq!(calibration_value(&line), None);
+        line[17] = b'3';
+        assert_eq!(first_digit(&line), Some(17));
+        assert_eq!(last_digit(&line), Some(17));
+        assert_eq!(calibration_value(&line), Some(33));
+        // in the remainder at the front when scanning backwards
+        line[2] = b'7';
+        line[39] = b'1';
+        assert_eq!(first_digit(&line), Some(2));
+        assert_eq!(last_digit(&line), Some(39));
+        assert_eq!(calibration_value(&line), Some(71));
+        assert_eq!(calibration_value(b"a5"), Some(55));
+    }
+
+    #[test]
+    fn examples_with_bytes() {
+        assert_eq!(calibration_value_detect_str(b"two1nine"), Some(29));
+        assert_eq!(calibration_value_detect_str(b"eighttwothree"), Some(83));
+        assert_eq!(calibration_value_detect_str(b"abcone2threexyz"), Some(13));
+        assert_eq!(calibration_value_detect_str(b"xtwoone3four"), Some(24));
+        assert_eq!(calibration_value_detect_str(b"4nineeightseven2"), Some(42));
+        assert_eq!(calibration_value_detect_str(b"zoneight234"), Some(14));
+        assert_eq!(calibration_value_detect_str(b"7pqrstsixteen"), Some(76));
+        assert_eq!(calibration_value_detect_str(b"eightwo"), Some(82));
+        assert_eq!(calibration_value_detect_str(b"abcnine"), Some(99));
+        assert_eq!(calibration_value_detect_str(b"xyz"), None);
+    }
+
+    #[test]
+    fn test_errors_match_string_version() {
+        for input in ["1a\n\n2b\n", "1a\r\n\r\n", "1a\nxyz\n3c", "\n", "12\n"] {
+            assert_eq!(
+                try_solve_part_one_with_bytes(input),
+                crate::day01::try_solve_part_one(input),
+                "{:?}",
+                input
+            );
+            assert_eq!(
+                try_solve_part_two_with_bytes(input),
+                crate::day01::try_solve_part_two(input),
+                "{:?}",
+                input
+            );
+        }
+        let err = try_solve_part_one_with_bytes("1a\n\n2b\n").unwrap_err();
+        assert_eq!(err, NoDigitError { line: 2 });
+    }
+
+    #[test]
+    fn test_matches_string_version() {
+        let input = include_str!("../input/day01.in");
+        assert_eq!(
+            solve_part_one_with_bytes(input),
+            crate::day01::solve_part_one(input)
+        );
+        assert_eq!(
+            solve_part_two_with_bytes(input),
+            crate::day01::solve_part_two(input)
+        );
+    }
+}
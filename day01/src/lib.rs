@@ -1 +1,4 @@
 pub mod day01;
+pub mod day01_bytes;
+pub mod report;
+pub mod vocabulary;
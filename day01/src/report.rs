@@ -0,0 +1,155 @@
+use crate::vocabulary::{DigitMatcher, Token, TokenKind};
+use std::fmt;
+
+/// The tokens a calibration value was made from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    pub first: Token,
+    pub last: Token,
+    pub value: u64,
+}
+
+/// How one line was read by each part, `None` where a part found no digit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineReport<'a> {
+    /// Line number, starting at 1.
+    pub number: usize,
+    pub line: &'a str,
+    pub part_one: Option<Calibration>,
+    pub part_two: Option<Calibration>,
+}
+
+impl LineReport<'_> {
+    /// Whether spelled out digits changed the value of this line.
+    pub fn disagrees(&self) -> bool {
+        self.part_one.map(|c| c.value) != self.part_two.map(|c| c.value)
+    }
+}
+
+fn calibrate(matcher: &DigitMatcher, line: &str) -> Option<Calibration> {
+    Some(Calibration {
+        first: matcher.first(line)?,
+        last: matcher.last(line)?,
+        value: matcher.calibration_value(line)?,
+    })
+}
+
+/// Report for every line of `input` using the part one and part two rules.
+pub fn calibration_report(input: &str) -> Vec<LineReport<'_>> {
+    calibration_report_with(input, &DigitMatcher::english())
+}
+
+/// Report for every line of `input`, with part two reading digits using
+/// `matcher` instead of the English words.
+pub fn calibration_report_with<'a>(input: &'a str, matcher: &DigitMatcher) -> Vec<LineReport<'a>> {
+    let numerals = DigitMatcher::numerals();
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| LineReport {
+            number: i + 1,
+            line,
+            part_one: calibrate(&numerals, line),
+            part_two: calibrate(matcher, line),
+        })
+        .collect()
+}
+
+struct TokenDisplay<'a>(&'a str, Token);
+
+impl fmt::Display for TokenDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let TokenDisplay(line, token) = self;
+        let kind = match token.kind {
+            TokenKind::Numeral => "numeral",
+            TokenKind::Word => "word",
+        };
+        write!(
+            f,
+            "{:?} {}..{} {}",
+            token.text(line),
+            token.start,
+            token.end,
+            kind
+        )
+    }
+}
+
+struct CalibrationDisplay<'a>(&'a str, Option<Calibration>);
+
+impl fmt::Display for CalibrationDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.1 {
+            Some(c) => write!(
+                f,
+                "{} ({}, {})",
+                c.value,
+                TokenDisplay(self.0, c.first),
+                TokenDisplay(self.0, c.last)
+            ),
+            None => write!(f, "no digit"),
+        }
+    }
+}
+
+/// `12: "two1nine" part one 11 ("1" 3..4 numeral, ...) part two 29 (...)`
+impl fmt::Display for LineReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {:?} part one {} part two {}",
+            self.number,
+            self.line,
+            CalibrationDisplay(self.line, self.part_one),
+            CalibrationDisplay(self.line, self.part_two)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calibration_report() {
+        let report = calibration_report("two1nine\nabc\n7pqrstsixteen\n");
+        assert_eq!(report.len(), 3);
+
+        let first = &report[0];
+        assert_eq!(first.number, 1);
+        let part_one = first.part_one.unwrap();
+        assert_eq!((part_one.first.start, part_one.first.end), (3, 4));
+        assert_eq!(part_one.first.kind, TokenKind::Numeral);
+        assert_eq!(part_one.value, 11);
+        let part_two = first.part_two.unwrap();
+        assert_eq!(part_two.first.text(first.line), "two");
+        assert_eq!(part_two.first.kind, TokenKind::Word);
+        assert_eq!((part_two.last.start, part_two.last.end), (4, 8));
+        assert_eq!(part_two.value, 29);
+        assert!(first.disagrees());
+
+        assert_eq!(report[1].part_one, None);
+        assert_eq!(report[1].part_two, None);
+        assert!(!report[1].disagrees());
+        assert_eq!(
+            report[1].to_string(),
+            "2: \"abc\" part one no digit part two no digit"
+        );
+
+        assert!(report[2].disagrees());
+        assert_eq!(
+            report[2].to_string(),
+            "3: \"7pqrstsixteen\" part one 77 (\"7\" 0..1 numeral, \"7\" 0..1 numeral) \
+             part two 76 (\"7\" 0..1 numeral, \"six\" 6..9 word)"
+        );
+    }
+
+    #[test]
+    fn test_report_matches_solvers() {
+        let input = include_str!("../../examples/day01/example_two.in");
+        let report = calibration_report(input);
+        let part_two: u64 = report.iter().map(|r| r.part_two.unwrap().value).sum();
+        assert_eq!(part_two, 281);
+        assert_eq!(report.iter().filter(|r| r.disagrees()).count(), 6);
+    }
+}
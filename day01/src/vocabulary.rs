@@ -0,0 +1,300 @@
+use crate::day01::NoDigitError;
+use aho_corasick::{AhoCorasick, MatchKind};
+use rayon::prelude::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+
+pub const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Numeral,
+    Word,
+}
+
+/// A digit or word found in a line, `start..end` is its byte span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Token {
+    pub start: usize,
+    pub end: usize,
+    pub value: u32,
+    pub kind: TokenKind,
+}
+
+impl Token {
+    /// The text of the token in the line it was found in.
+    pub fn text<'a>(&self, line: &'a str) -> &'a str {
+        &line[self.start..self.end]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VocabularyError {
+    EmptyWord,
+    /// The same word was given two different values.
+    Conflict {
+        word: String,
+        first: u32,
+        second: u32,
+    },
+    Build(String),
+}
+
+impl fmt::Display for VocabularyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VocabularyError::EmptyWord => write!(f, "vocabulary contains an empty word"),
+            VocabularyError::Conflict {
+                word,
+                first,
+                second,
+            } => write!(f, "`{}` is both {} and {}", word, first, second),
+            VocabularyError::Build(e) => write!(f, "could not build matcher: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for VocabularyError {}
+
+thread_local! {
+    static REVERSED: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Finds the first and last digit in a line, where a digit is an ASCII
+/// numeral or any word of a word→value vocabulary.
+///
+/// The words are compiled into two Aho-Corasick automatons, one over the
+/// words and one over the reversed words, so the first token is the leftmost
+/// match of a forward search and the last token is the leftmost match of a
+/// search over the reversed line. Words may overlap (`eightwo` starts with 8
+/// and ends with 2), and when several words end at the same place the longest
+/// one wins.
+pub struct DigitMatcher {
+    forward: AhoCorasick,
+    backward: AhoCorasick,
+    tokens: Vec<(u32, TokenKind)>,
+}
+
+impl DigitMatcher {
+    /// Only the numerals `0` to `9`, as in part one.
+    pub fn numerals() -> DigitMatcher {
+        DigitMatcher::new::<_, &str>([]).expect("numerals to be a valid vocabulary")
+    }
+
+    /// The numerals plus `one` to `nine`, as in part two.
+    pub fn english() -> DigitMatcher {
+        DigitMatcher::new(ENGLISH).expect("english to be a valid vocabulary")
+    }
+
+    /// The numerals plus every word in `words`. Values are not limited to a
+    /// single digit, so `("ten", 10)` is allowed.
+    pub fn new<I, S>(words: I) -> Result<DigitMatcher, VocabularyError>
+    where
+        I: IntoIterator<Item = (S, u32)>,
+        S: AsRef<str>,
+    {
+        let mut patterns: Vec<String> = (0..10).map(|d| d.to_string()).collect();
+        let mut tokens: Vec<(u32, TokenKind)> = (0..10).map(|d| (d, TokenKind::Numeral)).collect();
+        let mut seen: HashMap<String, u32> = (0..10).map(|d| (d.to_string(), d)).collect();
+
+        for (word, value) in words {
+            let word = word.as_ref();
+            if word.is_empty() {
+                return Err(VocabularyError::EmptyWord);
+            }
+            match seen.get(word) {
+                Some(&first) if first != value => {
+                    return Err(VocabularyError::Conflict {
+                        word: word.to_string(),
+                        first,
+                        second: value,
+                    })
+                }
+                Some(_) => continue,
+                None => {
+                    seen.insert(word.to_string(), value);
+                }
+            }
+            patterns.push(word.to_string());
+            tokens.push((value, TokenKind::Word));
+        }
+
+        let reversed = patterns
+            .iter()
+            .map(|p| p.bytes().rev().collect::<Vec<u8>>());
+        let build = |kind, patterns: Vec<Vec<u8>>| {
+            AhoCorasick::builder()
+                .match_kind(kind)
+                .build(patterns)
+                .map_err(|e| VocabularyError::Build(e.to_string()))
+        };
+        Ok(DigitMatcher {
+            forward: build(
+                MatchKind::LeftmostLongest,
+                patterns.iter().map(|p| p.as_bytes().to_vec()).collect(),
+            )?,
+            backward: build(MatchKind::LeftmostLongest, reversed.collect())?,
+            tokens,
+        })
+    }
+
+    fn token(&self, pattern: usize, start: usize, end: usize) -> Token {
+        let (value, kind) = self.tokens[pattern];
+        Token {
+            start,
+            end,
+            value,
+            kind,
+        }
+    }
+
+    pub fn first(&self, line: &str) -> Option<Token> {
+        let m = self.forward.find(line)?;
+        Some(self.token(m.pattern().as_usize(), m.start(), m.end()))
+    }
+
+    pub fn last(&self, line: &str) -> Option<Token> {
+        // the reversed line goes into a buffer kept per thread, so searching
+        // line after line only allocates when a line is longer than any before
+        let m = REVERSED.with_borrow_mut(|reversed| {
+            reversed.clear();
+            reversed.extend(line.bytes().rev());
+            self.backward.find(reversed.as_slice())
+        })?;
+        Some(self.token(
+            m.pattern().as_usize(),
+            line.len() - m.end(),
+            line.len() - m.start(),
+        ))
+    }
+
+    /// The first and last token written one after the other, so with `ten`
+    /// in the vocabulary `ten...3` is 103. `None` if the line has no tokens.
+    pub fn calibration_value(&self, line: &str) -> Option<u64> {
+        let first = self.first(line)?.value as u64;
+        let last = self.last(line)?.value as u64;
+        let shift = 10u64.pow(last.checked_ilog10().unwrap_or(0) + 1);
+        Some(first * shift + last)
+    }
+}
+
+/// Sum of the calibration values of every line using `matcher`, or the
+/// first line without a token.
+pub fn solve_with_vocabulary(input: &str, matcher: &DigitMatcher) -> Result<u64, NoDigitError> {
+    input
+        .par_lines()
+        .map(|line| matcher.calibration_value(line))
+        .sum::<Option<u64>>()
+        .ok_or_else(|| {
+            let i = input
+                .lines()
+                .position(|line| matcher.calibration_value(line).is_none());
+            NoDigitError {
+                line: i.expect("a line without a digit") + 1,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_english() {
+        let matcher = DigitMatcher::english();
+        assert_eq!(matcher.calibration_value("two1nine"), Some(29));
+        assert_eq!(matcher.calibration_value("zoneight234"), Some(14));
+        assert_eq!(matcher.calibration_value("eightwo"), Some(82));
+        assert_eq!(matcher.calibration_value("abcnine"), Some(99));
+        assert_eq!(matcher.calibration_value("xyz"), None);
+
+        let input = include_str!("../../examples/day01/example_two.in");
+        assert_eq!(solve_with_vocabulary(input, &matcher), Ok(281));
+        let input = include_str!("../../examples/day01/example_one.in");
+        assert_eq!(
+            solve_with_vocabulary(input, &DigitMatcher::numerals()),
+            Ok(142)
+        );
+        assert_eq!(
+            solve_with_vocabulary("two\nabc\n", &matcher),
+            Err(NoDigitError { line: 2 })
+        );
+        assert_eq!(
+            solve_with_vocabulary("1\n\n", &matcher),
+            Err(NoDigitError { line: 2 })
+        );
+    }
+
+    #[test]
+    fn test_token_spans() {
+        let matcher = DigitMatcher::english();
+        assert_eq!(
+            matcher.first("xeightwo"),
+            Some(Token {
+                start: 1,
+                end: 6,
+                value: 8,
+                kind: TokenKind::Word
+            })
+        );
+        assert_eq!(
+            matcher.last("xeightwo"),
+            Some(Token {
+                start: 5,
+                end: 8,
+                value: 2,
+                kind: TokenKind::Word
+            })
+        );
+        assert_eq!(matcher.last("é7é").map(|t| (t.start, t.end)), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_other_vocabularies() {
+        let german =
+            DigitMatcher::new([("eins", 1), ("zwei", 2), ("drei", 3), ("zehn", 10)]).unwrap();
+        assert_eq!(german.calibration_value("xzweinsx"), Some(21));
+        assert_eq!(german.calibration_value("zehn5drei"), Some(103));
+        assert_eq!(german.calibration_value("two"), None);
+
+        let with_zero = DigitMatcher::new([("zero", 0), ("nine", 9)]).unwrap();
+        assert_eq!(with_zero.calibration_value("zeronine"), Some(9));
+        assert_eq!(with_zero.calibration_value("ninezero"), Some(90));
+
+        // the longest word wins when two end at the same place
+        let suffixes = DigitMatcher::new([("seventeen", 17), ("teen", 13)]).unwrap();
+        assert_eq!(suffixes.last("seventeen").map(|t| t.value), Some(17));
+
+        let accents = DigitMatcher::new([("três", 3), ("um", 1)]).unwrap();
+        assert_eq!(accents.calibration_value("umxtrês"), Some(13));
+    }
+
+    #[test]
+    fn test_invalid_vocabulary() {
+        assert_eq!(
+            DigitMatcher::new([("", 1)]).err(),
+            Some(VocabularyError::EmptyWord)
+        );
+        assert_eq!(
+            DigitMatcher::new([("one", 1), ("one", 2)]).err(),
+            Some(VocabularyError::Conflict {
+                word: "one".to_string(),
+                first: 1,
+                second: 2
+            })
+        );
+        assert!(DigitMatcher::new([("one", 1), ("one", 1)]).is_ok());
+        assert!(DigitMatcher::new([("7", 8)]).is_err());
+    }
+}
@@ -0,0 +1,15 @@
+//! Runs both day01 implementations against every example in `examples/day01`.
+
+mod string {
+    use aoc_2023::day01::*;
+
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
+
+mod bytes {
+    use aoc_2023::day01_bytes::{
+        solve_part_one_with_bytes as solve_part_one, solve_part_two_with_bytes as solve_part_two,
+    };
+
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+}
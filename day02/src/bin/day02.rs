@@ -1,9 +1,15 @@
 use aoc_2023::day02::*;
 
 fn main() {
-    let p1_answer = solve_part_one(include_str!("../../input/day02.in"));
+    // `day02 [split|iterator|bytes]` picks the parsing strategy
+    let strategy = match std::env::args().nth(1) {
+        Some(name) => name.parse().unwrap_or_else(|e| panic!("{}", e)),
+        None => Strategy::default(),
+    };
+
+    let p1_answer = solve_part_one_with(include_str!("../../input/day02.in"), strategy);
     println!("p1: {}", p1_answer);
 
-    let p2_answer = solve_part_two(include_str!("../../input/day02.in"));
+    let p2_answer = solve_part_two_with(include_str!("../../input/day02.in"), strategy);
     println!("p2: {}", p2_answer);
 }
@@ -1,9 +0,0 @@
-use aoc_2023::day02_iter::*;
-
-fn main() {
-    let p1_answer = solve_part_one_with_iterator(include_str!("../../input/day02.in"));
-    println!("p1: {}", p1_answer);
-
-    let p2_answer = solve_part_two_with_iterator(include_str!("../../input/day02.in"));
-    println!("p2: {}", p2_answer);
-}
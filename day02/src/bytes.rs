@@ -0,0 +1,125 @@
+use crate::game::{CubeColor, CubeSet, Game};
+use aoc_common::parse::ParseError;
+
+/// Walks the bytes of one line, never copying or splitting it.
+struct Cursor<'a> {
+    source: &'a str,
+    line: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.line.as_bytes().get(self.pos).copied()
+    }
+
+    fn error(&self, message: &str) -> ParseError {
+        ParseError::at(self.source, &self.line[self.pos..], message)
+    }
+
+    fn skip_spaces(&mut self) -> usize {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b' ' | b'\t')) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    fn expect(&mut self, literal: &[u8], message: &str) -> Result<(), ParseError> {
+        if !self.line.as_bytes()[self.pos..].starts_with(literal) {
+            return Err(self.error(message));
+        }
+        self.pos += literal.len();
+        Ok(())
+    }
+
+    fn number(&mut self, message: &str) -> Result<u32, ParseError> {
+        let start = self.pos;
+        let mut value: u32 = 0;
+        while let Some(b @ b'0'..=b'9') = self.peek() {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((b - b'0') as u32))
+                .ok_or_else(|| self.error("number too large"))?;
+            self.pos += 1;
+        }
+        if self.pos == start {
+            return Err(self.error(message));
+        }
+        Ok(value)
+    }
+
+    fn name(&mut self) -> &'a str {
+        let start = self.pos;
+        while self.peek().is_some_and(|b| b.is_ascii_alphabetic()) {
+            self.pos += 1;
+        }
+        &self.line[start..self.pos]
+    }
+}
+
+/// Parse a `Game N: ...` line in a single pass over its bytes. `line` must be
+/// a slice of `source` so that errors point at the right line of the input.
+pub(crate) fn parse_game<'a>(source: &'a str, line: &'a str) -> Result<Game, ParseError> {
+    let mut cursor = Cursor {
+        source,
+        line,
+        pos: 0,
+    };
+    cursor.expect(b"Game ", "expected Game")?;
+    cursor.skip_spaces();
+    let id = cursor.number("expected game id")?;
+    cursor.skip_spaces();
+    cursor.expect(b":", "expected ':'")?;
+
+    let mut sets = vec![];
+    let mut cubes = vec![];
+    loop {
+        cursor.skip_spaces();
+        let count = cursor.number("expected cube count")?;
+        if cursor.skip_spaces() == 0 {
+            return Err(cursor.error("expected ' '"));
+        }
+        let color = match cursor.name().parse::<CubeColor>() {
+            Ok(color) => color,
+            Err(()) => return Err(cursor.error("expected colour")),
+        };
+        cubes.push((color, u64::from(count)));
+
+        cursor.skip_spaces();
+        match cursor.peek() {
+            Some(b',') => cursor.pos += 1,
+            Some(b';') => {
+                cursor.pos += 1;
+                sets.push(CubeSet::new(std::mem::take(&mut cubes)));
+            }
+            None | Some(b'\r') if cursor.line[cursor.pos..].trim().is_empty() => {
+                sets.push(CubeSet::new(cubes));
+                return Ok(Game { id, sets });
+            }
+            _ => return Err(cursor.error("expected ',' or ';'")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_parse_error() {
+        let input = "Game 1: 3 blue\nGame 2: 1 blue, purple";
+        let (_, second) = input.split_once('\n').unwrap();
+        let err = parse_game(input, second).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 17));
+        assert_eq!(err.message, "expected cube count");
+
+        let err = parse_game("Game 1: 3 blue. 4 red", "Game 1: 3 blue. 4 red").unwrap_err();
+        assert_eq!(
+            (err.column, err.message.as_str()),
+            (15, "expected ',' or ';'")
+        );
+        let err = parse_game("Game 1: 3blue", "Game 1: 3blue").unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (10, "expected ' '"));
+    }
+}
@@ -1,124 +1,261 @@
-use std::cmp;
+use crate::game::{CubeSet, Game};
+use aoc_common::parse::ParseError;
 use rayon::prelude::*;
+use std::fmt;
+use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq)]
-struct GameInfo {
-    id: u32,
-    max_blue: u32,
-    max_red: u32,
-    max_green: u32,
+/// How to read a `Game N: ...` line. All strategies build the same [`Game`]
+/// and report errors at the same place, they only differ in speed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strategy {
+    /// `str::split` on the punctuation.
+    Split,
+    /// `nom` parsers driven by an iterator over the cube counts.
+    Iterator,
+    /// One pass over the bytes of the line without splitting it.
+    #[default]
+    Bytes,
 }
 
-pub fn solve_part_one(input: &str) -> u32 {
-    input
-        .par_lines()
-        .map(get_game_totals)
-        .filter(|gc| gc.max_red <= 12 && gc.max_green <= 13 && gc.max_blue <= 14)
-        .map(|gc| gc.id)
-        .sum()
+impl Strategy {
+    pub const ALL: [Strategy; 3] = [Strategy::Split, Strategy::Iterator, Strategy::Bytes];
+
+    /// Parse one line, which must be a slice of `source` so that errors
+    /// point at the right line of the input.
+    pub fn parse_game<'a>(self, source: &'a str, line: &'a str) -> Result<Game, ParseError> {
+        match self {
+            Strategy::Split => crate::split::parse_game(source, line),
+            Strategy::Iterator => crate::iter::parse_game(source, line),
+            Strategy::Bytes => crate::bytes::parse_game(source, line),
+        }
+    }
 }
 
-pub fn solve_part_two(input: &str) -> u32 {
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Strategy::Split => "split",
+            Strategy::Iterator => "iterator",
+            Strategy::Bytes => "bytes",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Strategy {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Strategy, String> {
+        Strategy::ALL
+            .into_iter()
+            .find(|s| s.to_string() == input)
+            .ok_or_else(|| format!("unknown strategy `{}`", input))
+    }
+}
+
+/// Every game in `input`.
+pub fn parse_games(input: &str, strategy: Strategy) -> Result<Vec<Game>, ParseError> {
     input
         .par_lines()
-        .map(get_game_totals)
-        .map(|gc| gc.max_red * gc.max_green * gc.max_blue)
-        .sum()
+        .map(|line| strategy.parse_game(input, line))
+        .collect()
 }
 
-fn get_game_totals(line: &str) -> GameInfo {
-    let tokens: Vec<&str> = line.split([':', ',', ';']).collect();
-    let id = tokens[0][5..tokens[0].len()].parse::<u32>().unwrap();
-
-    let mut red = 0;
-    let mut green = 0;
-    let mut blue = 0;
-    for token in tokens[1..].iter() {
-        let info: Vec<&str> = token[1..].split_whitespace().collect();
-        let count = info[0].parse::<u32>().unwrap();
-        match info[1].chars().next().unwrap() {
-            'b' => blue = cmp::max(count, blue),
-            'g' => green = cmp::max(count, green),
-            'r' => red = cmp::max(count, red),
-            _ => panic!("encountered unexpected color character"),
-        }
-    }
+fn games(input: &str, strategy: Strategy) -> impl ParallelIterator<Item = Game> + '_ {
+    input.par_lines().map(move |line| {
+        strategy
+            .parse_game(input, line)
+            .unwrap_or_else(|e| panic!("{}", e))
+    })
+}
 
-    GameInfo {
-        id,
-        max_blue: blue,
-        max_red: red,
-        max_green: green,
-    }
+pub fn solve_part_one(input: &str) -> u32 {
+    solve_part_one_with(input, Strategy::default())
+}
+
+pub fn solve_part_two(input: &str) -> u64 {
+    solve_part_two_with(input, Strategy::default())
 }
 
+pub fn solve_part_one_with(input: &str, strategy: Strategy) -> u32 {
+    solve_part_one_with_bag(input, &CubeSet::puzzle_bag(), strategy)
+}
+
+/// Sum of the ids of the games that could have been played with `bag`.
+pub fn solve_part_one_with_bag(input: &str, bag: &CubeSet, strategy: Strategy) -> u32 {
+    games(input, strategy)
+        .filter(|game| game.is_feasible(bag))
+        .map(|game| game.id)
+        .sum()
+}
+
+/// Sum of the powers of the smallest bags, panics if it does not fit in a
+/// `u64`.
+pub fn solve_part_two_with(input: &str, strategy: Strategy) -> u64 {
+    games(input, strategy)
+        .map(|game| game.minimum_bag().power())
+        .try_reduce(|| 0, u64::checked_add)
+        .unwrap_or_else(|| panic!("sum of powers does not fit in a u64"))
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::game::CubeColor;
+
+    const EXAMPLE_INPUT: &str = include_str!("../../examples/day02/example.in");
+
+    const GAME_FOUR: &str =
+        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red";
 
-            const EXAMPLE_INPUT: &str = r#"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
-"#;
+    const GAME_EIGHTY_NINE: &str = "Game 89: 13 blue, 6 red, 15 green; 5 green, 14 blue, 9 red; 3 green, 15 blue, 5 red; 13 red, 13 green; 18 red, 4 green, 19 blue; 10 green, 10 red, 18 blue";
 
     #[test]
-    fn test_solve_part_one_example() {
-        assert_eq!(
-            solve_part_one(EXAMPLE_INPUT),
-            8
-        )
+    fn test_solve_examples() {
+        for strategy in Strategy::ALL {
+            assert_eq!(
+                solve_part_one_with(EXAMPLE_INPUT, strategy),
+                8,
+                "{}",
+                strategy
+            );
+            assert_eq!(
+                solve_part_two_with(EXAMPLE_INPUT, strategy),
+                2286,
+                "{}",
+                strategy
+            );
+        }
+    }
+
+    #[test]
+    fn test_large_powers() {
+        let input =
+            "Game 1: 4000000000 red, 4000000000 green, 1 blue\nGame 2: 1 red, 1 green, 1 blue";
+        for strategy in Strategy::ALL {
+            assert_eq!(
+                solve_part_two_with(input, strategy),
+                16_000_000_000_000_000_001
+            );
+        }
     }
 
     #[test]
-    fn test_solve_part_two_example() {
-        assert_eq!(
-            solve_part_two(EXAMPLE_INPUT),
-            2286 
-        )
+    #[should_panic(expected = "sum of powers does not fit in a u64")]
+    fn test_power_overflow() {
+        solve_part_two("Game 1: 4000000000 red, 4000000000 green, 4000000000 blue");
     }
 
     #[test]
-    fn test_game_count() {
-        assert_eq!(
-            get_game_totals(
-                "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red"
-            ),
-            GameInfo {
-                id: 4,
-                max_green: 3,
-                max_red: 14,
-                max_blue: 15, 
+    fn test_strategies_agree() {
+        let input = include_str!("../input/day02.in");
+        let expected = parse_games(input, Strategy::Split).unwrap();
+        for strategy in Strategy::ALL {
+            assert_eq!(
+                parse_games(input, strategy).unwrap(),
+                expected,
+                "{}",
+                strategy
+            );
+        }
+    }
+
+    #[test]
+    fn test_minimum_bag() {
+        for strategy in Strategy::ALL {
+            let game = strategy.parse_game(GAME_FOUR, GAME_FOUR).unwrap();
+            assert_eq!(game.id, 4);
+            assert_eq!(game.minimum_bag().to_string(), "3 green, 14 red, 15 blue");
+
+            let game = strategy
+                .parse_game(GAME_EIGHTY_NINE, GAME_EIGHTY_NINE)
+                .unwrap();
+            assert_eq!(game.id, 89);
+            assert_eq!(game.minimum_bag().to_string(), "19 blue, 18 red, 15 green");
+        }
+    }
+
+    #[test]
+    fn test_custom_bag() {
+        let input = "Game 1: 3 blue, 2 gold\nGame 2: 1 blue; 9 gold\nGame 3: 4 red";
+        for strategy in Strategy::ALL {
+            let bag = CubeSet::from_counts([("blue", 3), ("gold", 2)]);
+            assert_eq!(solve_part_one_with_bag(input, &bag, strategy), 1);
+            let bag = "3 blue, 9 gold, 4 red".parse().unwrap();
+            assert_eq!(solve_part_one_with_bag(input, &bag, strategy), 6);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("Game 1: 3 blue\nGame 2: 1 blue, purple", (2, 17)),
+            ("Game 1: 3 blue\nGame 2: 1 blue, x red", (2, 17)),
+            ("Gme 1: 3 blue", (1, 1)),
+            ("Game 1: 3 blue,", (1, 16)),
+            ("Game 1: 3 blue;", (1, 16)),
+            ("Game 1: 3 blue; ; 2 red", (1, 17)),
+            ("Game 1: ", (1, 9)),
+        ];
+        for strategy in Strategy::ALL {
+            for (input, position) in cases {
+                let err = parse_games(input, strategy).unwrap_err();
+                assert_eq!((err.line, err.column), position, "{} {:?}", strategy, input);
             }
-        );
-
-        assert_eq!(
-            get_game_totals(
-                "Game 89: 13 blue, 6 red, 15 green; 5 green, 14 blue, 9 red; 3 green, 15 blue, 5 red; 13 red, 13 green; 18 red, 4 green, 19 blue; 10 green, 10 red, 18 blue"
-            ),
-            GameInfo {
-                id: 89,
-                max_green: 15,
-                max_red: 18,
-                max_blue: 19 
+        }
+    }
+
+    #[test]
+    fn test_tabs() {
+        for line in [
+            "Game 1:\t3\tblue,\t2 red",
+            "Game 1: 3 blue\t, 2 red\t;\t1 green",
+        ] {
+            for strategy in Strategy::ALL {
+                let game = strategy.parse_game(line, line).unwrap();
+                assert_eq!(
+                    game.sets[0].cubes,
+                    [(CubeColor::BLUE, 3), (CubeColor::RED, 2)],
+                    "{}",
+                    strategy
+                );
             }
-        )
+        }
     }
 
     #[test]
-    fn test_game_count_with_iter() {
-        assert_eq!(
-            get_game_totals(
-                "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red"
-            ),
-            GameInfo {
-                id: 4,
-                max_green: 3,
-                max_red: 14,
-                max_blue: 15, 
+    fn test_color_names_agree() {
+        // only ASCII letters make a colour, whichever parser reads it
+        for line in [
+            "Game 1: 2 rød",
+            "Game 1: 2 re-d",
+            "Game 1: 2 red2",
+            "Game 1: 2 _",
+        ] {
+            for strategy in Strategy::ALL {
+                assert!(
+                    strategy.parse_game(line, line).is_err(),
+                    "{} {:?}",
+                    strategy,
+                    line
+                );
             }
-        );
+            let (_, name) = line.rsplit_once(' ').unwrap();
+            assert!(name.parse::<CubeColor>().is_err(), "{:?}", name);
+        }
+        for strategy in Strategy::ALL {
+            let game = strategy
+                .parse_game("Game 1: 2 Teal", "Game 1: 2 Teal")
+                .unwrap();
+            assert_eq!(game.sets[0].cubes, [(CubeColor::new("Teal"), 2)]);
+        }
+    }
+
+    #[test]
+    fn test_strategy_names() {
+        for strategy in Strategy::ALL {
+            assert_eq!(strategy.to_string().parse(), Ok(strategy));
+        }
+        assert!("regex".parse::<Strategy>().is_err());
     }
 }
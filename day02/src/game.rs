@@ -0,0 +1,308 @@
+use crate::day02::Strategy;
+use aoc_common::parse::ParseError;
+use std::cmp;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// A cube colour. The puzzle's three colours are plain variants, so they are
+/// cheap to compare, and any other colour carries its own name.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CubeColor(Name);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Name {
+    Red,
+    Green,
+    Blue,
+    /// Never one of the names above, so every colour has one representation.
+    Other(Arc<str>),
+}
+
+impl CubeColor {
+    pub const RED: CubeColor = CubeColor(Name::Red);
+    pub const GREEN: CubeColor = CubeColor(Name::Green);
+    pub const BLUE: CubeColor = CubeColor(Name::Blue);
+
+    /// The colour called `name`. Parse a name with `str::parse` instead to
+    /// check that it is a valid colour.
+    pub fn new(name: &str) -> CubeColor {
+        CubeColor(match name {
+            "red" => Name::Red,
+            "green" => Name::Green,
+            "blue" => Name::Blue,
+            other => Name::Other(other.into()),
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        match &self.0 {
+            Name::Red => "red",
+            Name::Green => "green",
+            Name::Blue => "blue",
+            Name::Other(name) => name,
+        }
+    }
+}
+
+/// Any non-empty ASCII alphabetic name is a colour. Every parser strategy
+/// accepts exactly these names.
+impl FromStr for CubeColor {
+    type Err = ();
+
+    fn from_str(input: &str) -> Result<CubeColor, Self::Err> {
+        if input.is_empty() || !input.bytes().all(|b| b.is_ascii_alphabetic()) {
+            return Err(());
+        }
+        Ok(CubeColor::new(input))
+    }
+}
+
+impl fmt::Display for CubeColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The bag from the puzzle, used by `solve_part_one`.
+pub const PUZZLE_BAG: &str = "12 red, 13 green, 14 blue";
+
+/// One handful of cubes, in the order they were listed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CubeSet {
+    pub cubes: Vec<(CubeColor, u64)>,
+}
+
+impl CubeSet {
+    pub fn new(cubes: Vec<(CubeColor, u64)>) -> CubeSet {
+        CubeSet { cubes }
+    }
+
+    /// A set (or bag) from colour names and counts, e.g.
+    /// `CubeSet::from_counts([("red", 12), ("green", 13)])`.
+    pub fn from_counts<'a>(counts: impl IntoIterator<Item = (&'a str, u64)>) -> CubeSet {
+        CubeSet::new(
+            counts
+                .into_iter()
+                .map(|(name, count)| (CubeColor::new(name), count))
+                .collect(),
+        )
+    }
+
+    /// The bag the puzzle asks about in part one, see [`PUZZLE_BAG`].
+    pub fn puzzle_bag() -> CubeSet {
+        PUZZLE_BAG.parse().expect("puzzle bag to be valid")
+    }
+
+    /// Number of `color` cubes, 0 if the colour is not listed.
+    pub fn count(&self, color: &CubeColor) -> u64 {
+        self.cubes
+            .iter()
+            .filter(|(c, _)| c == color)
+            .map(|(_, n)| n)
+            .sum()
+    }
+
+    /// Whether this set could have been drawn from `bag`. Colours that are
+    /// not in the bag have no cubes.
+    pub fn fits_in(&self, bag: &CubeSet) -> bool {
+        self.cubes
+            .iter()
+            .all(|(color, _)| self.count(color) <= bag.count(color))
+    }
+
+    /// Product of the red, green and blue counts, `None` if it does not fit
+    /// in a `u64`.
+    pub fn power(&self) -> Option<u64> {
+        self.count(&CubeColor::RED)
+            .checked_mul(self.count(&CubeColor::GREEN))?
+            .checked_mul(self.count(&CubeColor::BLUE))
+    }
+}
+
+/// `3 blue, 4 red`
+impl fmt::Display for CubeSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (color, count)) in self.cubes.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} {}", count, color)?;
+        }
+        Ok(())
+    }
+}
+
+/// `12 red, 13 green, 14 blue`, useful for reading bag configurations.
+impl FromStr for CubeSet {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<CubeSet, ParseError> {
+        crate::iter::parse_cube_set(input, input)
+    }
+}
+
+/// Every set revealed in one game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    pub id: u32,
+    pub sets: Vec<CubeSet>,
+}
+
+/// How many cubes of one colour were shown across the sets of a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColorStats {
+    /// Number of sets the colour was listed in.
+    pub shown: usize,
+    /// Fewest cubes in a set, counting sets without the colour as 0.
+    pub min: u64,
+    pub max: u64,
+    pub total: u64,
+}
+
+impl Game {
+    /// The smallest bag every set could have come from, with the colours in
+    /// the order they first appear.
+    pub fn minimum_bag(&self) -> CubeSet {
+        let mut bag: Vec<(CubeColor, u64)> = vec![];
+        for set in &self.sets {
+            for (color, _) in &set.cubes {
+                let count = set.count(color);
+                match bag.iter_mut().find(|(c, _)| c == color) {
+                    Some((_, max)) => *max = cmp::max(*max, count),
+                    None => bag.push((color.clone(), count)),
+                }
+            }
+        }
+        CubeSet::new(bag)
+    }
+
+    /// Whether every set could have been drawn from `bag`.
+    pub fn is_feasible(&self, bag: &CubeSet) -> bool {
+        self.sets.iter().all(|set| set.fits_in(bag))
+    }
+
+    pub fn color_stats(&self, color: &CubeColor) -> ColorStats {
+        let counts = self.sets.iter().map(|set| set.count(color));
+        ColorStats {
+            shown: self
+                .sets
+                .iter()
+                .filter(|set| set.cubes.iter().any(|(c, _)| c == color))
+                .count(),
+            min: counts.clone().min().unwrap_or(0),
+            max: counts.clone().max().unwrap_or(0),
+            total: counts.sum(),
+        }
+    }
+}
+
+/// `Game 4: 1 green, 3 red; 3 green`
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}:", self.id)?;
+        for (i, set) in self.sets.iter().enumerate() {
+            let separator = if i == 0 { " " } else { "; " };
+            write!(f, "{}{}", separator, set)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Game {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Game, ParseError> {
+        Strategy::default().parse_game(line, line)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAME_FOUR: &str =
+        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red";
+
+    #[test]
+    fn test_round_trip() {
+        let game: Game = GAME_FOUR.parse().unwrap();
+        assert_eq!(game.id, 4);
+        assert_eq!(game.sets.len(), 3);
+        assert_eq!(
+            game.sets[1],
+            CubeSet::new(vec![(CubeColor::GREEN, 3), (CubeColor::RED, 6)])
+        );
+        assert_eq!(game.to_string(), GAME_FOUR);
+
+        for line in include_str!("../../examples/day02/example.in").lines() {
+            assert_eq!(line.parse::<Game>().unwrap().to_string(), line);
+        }
+    }
+
+    #[test]
+    fn test_queries() {
+        let game: Game = GAME_FOUR.parse().unwrap();
+        let bag = game.minimum_bag();
+        assert_eq!(bag.to_string(), "3 green, 14 red, 15 blue");
+        assert_eq!(bag.power(), Some(630));
+        assert!(game.is_feasible(&bag));
+
+        assert!(!game.is_feasible(&CubeSet::puzzle_bag()));
+        assert!(game.is_feasible(&CubeSet::from_counts([
+            ("blue", 20),
+            ("red", 14),
+            ("green", 3)
+        ])));
+
+        assert_eq!(
+            game.color_stats(&CubeColor::BLUE),
+            ColorStats {
+                shown: 2,
+                min: 0,
+                max: 15,
+                total: 21
+            }
+        );
+    }
+
+    #[test]
+    fn test_large_counts() {
+        let set: CubeSet = "4000000000 red, 4000000000 red, 2 green, 1 blue"
+            .parse()
+            .unwrap();
+        assert_eq!(set.count(&CubeColor::RED), 8_000_000_000);
+        assert_eq!(set.power(), Some(16_000_000_000));
+
+        let game: Game = "Game 1: 4000000000 red; 4000000000 red, 4000000000 red"
+            .parse()
+            .unwrap();
+        assert_eq!(game.color_stats(&CubeColor::RED).total, 12_000_000_000);
+
+        let set: CubeSet = "4000000000 red, 4000000000 green, 4000000000 blue"
+            .parse()
+            .unwrap();
+        assert_eq!(set.power(), None);
+    }
+
+    #[test]
+    fn test_any_colors() {
+        let game: Game = "Game 7: 2 purple, 1 red; 5 teal; 3 purple".parse().unwrap();
+        assert_eq!(
+            game.to_string(),
+            "Game 7: 2 purple, 1 red; 5 teal; 3 purple"
+        );
+        let purple = CubeColor::new("purple");
+        assert_eq!(purple.name(), "purple");
+        assert_eq!("purple".parse(), Ok(purple.clone()));
+        assert_ne!(purple, CubeColor::new("teal"));
+        assert_eq!(game.color_stats(&purple).max, 3);
+        assert_eq!(game.minimum_bag().to_string(), "3 purple, 1 red, 5 teal");
+
+        let bag: CubeSet = "3 purple, 1 red, 5 teal".parse().unwrap();
+        assert!(game.is_feasible(&bag));
+        // without teal in the bag the second set is impossible
+        assert!(!game.is_feasible(&"3 purple, 1 red".parse().unwrap()));
+        assert!("3 purple, 1".parse::<CubeSet>().is_err());
+    }
+}
@@ -0,0 +1,109 @@
+use crate::game::{CubeColor, CubeSet, Game};
+use aoc_common::parse::{integer, numbered, PResult, ParseError};
+use nom::character::complete::{alpha1, char, space0, space1};
+use nom::combinator::map_res;
+use nom::error::{context, VerboseError};
+use nom::sequence::{preceded, separated_pair, terminated};
+use nom::Finish;
+use std::str::FromStr;
+
+type CubeCount = (CubeColor, u64);
+
+struct SetIterator<'a> {
+    data: &'a str,
+    done: bool,
+}
+
+fn set_iterator(data: &str) -> SetIterator<'_> {
+    SetIterator {
+        data: data.trim_start(),
+        done: false,
+    }
+}
+
+impl<'a> Iterator for SetIterator<'a> {
+    type Item = Result<CubeCount, VerboseError<&'a str>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        };
+        // every set holds at least one count and a comma must come before
+        // each further one, so `3 blue,` and an empty set are both errors
+        let result = terminated(cube_count, space0)(self.data)
+            .and_then(|(rest, count)| match rest {
+                "" => {
+                    self.done = true;
+                    Ok((rest, count))
+                }
+                _ => preceded(char(','), space0)(rest).map(|(rest, _)| (rest, count)),
+            })
+            .finish();
+        match result {
+            Ok((rest, count)) => {
+                self.data = rest;
+                Some(Ok(count))
+            }
+            Err(e) => {
+                // stop at the first malformed count
+                self.done = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+fn cube_count(input: &str) -> PResult<'_, CubeCount> {
+    context(
+        "cube count",
+        map_res(
+            separated_pair(integer::<u32>, space1, alpha1),
+            |(amount, color)| CubeColor::from_str(color).map(|c| (c, u64::from(amount))),
+        ),
+    )(input)
+}
+
+/// Parse a `Game N: ...` line. `line` must be a slice of `source` so that
+/// errors point at the right line of the input.
+pub(crate) fn parse_game<'a>(source: &'a str, line: &'a str) -> Result<Game, ParseError> {
+    let (sets_data, id) = numbered::<u32>("Game")(line)
+        .finish()
+        .map_err(|e| ParseError::from_verbose(source, e))?;
+
+    let sets = sets_data
+        .split(';')
+        .map(|set_data| parse_cube_set(source, set_data))
+        .collect::<Result<Vec<CubeSet>, ParseError>>()?;
+
+    Ok(Game { id, sets })
+}
+
+/// Parse `3 blue, 4 red`, `data` must be a slice of `source`.
+pub(crate) fn parse_cube_set<'a>(source: &'a str, data: &'a str) -> Result<CubeSet, ParseError> {
+    let cubes = set_iterator(data)
+        .map(|count| count.map_err(|e| ParseError::from_verbose(source, e)))
+        .collect::<Result<Vec<CubeCount>, ParseError>>()?;
+    Ok(CubeSet::new(cubes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_iterator() {
+        let counts: Vec<CubeCount> = set_iterator(" 3 blue, 4 red ")
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(counts, [(CubeColor::BLUE, 3), (CubeColor::RED, 4)]);
+    }
+
+    #[test]
+    fn test_game_parse_error() {
+        let input = "Game 1: 3 blue\nGame 2: 1 blue, purple";
+        let (_, second) = input.split_once('\n').unwrap();
+        let err = parse_game(input, second).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 17));
+        assert_eq!(err.message, "expected integer in cube count");
+    }
+}
@@ -1,2 +1,6 @@
 pub mod day02;
-pub mod day02_iter;
+pub mod game;
+
+mod bytes;
+mod iter;
+mod split;
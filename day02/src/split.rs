@@ -0,0 +1,58 @@
+use crate::game::{CubeColor, CubeSet, Game};
+use aoc_common::parse::ParseError;
+
+/// Parse a `Game N: ...` line by splitting on its punctuation. `line` must be
+/// a slice of `source` so that errors point at the right line of the input.
+pub(crate) fn parse_game<'a>(source: &'a str, line: &'a str) -> Result<Game, ParseError> {
+    let (game, sets_data) = line
+        .split_once(':')
+        .ok_or_else(|| ParseError::at(source, line, "expected Game"))?;
+    let id = game
+        .strip_prefix("Game ")
+        .and_then(|id| id.trim().parse::<u32>().ok())
+        .ok_or_else(|| ParseError::at(source, line, "expected Game"))?;
+
+    let sets = sets_data
+        .split(';')
+        .map(|set_data| {
+            let cubes = set_data
+                .split(',')
+                .map(|token| {
+                    let info: Vec<&str> = token.split_whitespace().collect();
+                    let count = info.first().and_then(|count| count.parse::<u32>().ok());
+                    let color = info
+                        .get(1)
+                        .and_then(|color| color.parse::<CubeColor>().ok());
+                    match (count, color, info.len()) {
+                        (Some(count), Some(color), 2) => Ok((color, u64::from(count))),
+                        _ => Err(ParseError::at(
+                            source,
+                            token.trim_start(),
+                            "expected cube count",
+                        )),
+                    }
+                })
+                .collect::<Result<Vec<(CubeColor, u64)>, ParseError>>()?;
+            Ok(CubeSet::new(cubes))
+        })
+        .collect::<Result<Vec<CubeSet>, ParseError>>()?;
+
+    Ok(Game { id, sets })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_game_parse_error() {
+        let input = "Game 1: 3 blue\nGame 2: 1 blue, purple";
+        let (_, second) = input.split_once('\n').unwrap();
+        let err = parse_game(input, second).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 17));
+        assert_eq!(err.message, "expected cube count");
+
+        let err = parse_game("Gmae 1: 3 blue", "Gmae 1: 3 blue").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 1));
+    }
+}
@@ -0,0 +1,23 @@
+//! Runs every day02 parsing strategy against every example in `examples/day02`.
+
+macro_rules! strategy_examples {
+    ($module:ident, $strategy:ident) => {
+        mod $module {
+            use aoc_2023::day02::{solve_part_one_with, solve_part_two_with, Strategy};
+
+            fn solve_part_one(input: &str) -> u32 {
+                solve_part_one_with(input, Strategy::$strategy)
+            }
+
+            fn solve_part_two(input: &str) -> u64 {
+                solve_part_two_with(input, Strategy::$strategy)
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+        }
+    };
+}
+
+strategy_examples!(split, Split);
+strategy_examples!(iterator, Iterator);
+strategy_examples!(bytes, Bytes);
@@ -1,4 +1,5 @@
 use aoc_2023::day03::*;
+use aoc_2023::day03_window::*;
 
 fn main() {
     // Run registered benchmarks.
@@ -10,7 +11,17 @@ fn day_three_part_one() {
     solve_part_one(divan::black_box(include_str!("../input/day03.in")));
 }
 
+#[divan::bench]
+fn day_three_part_one_with_window() {
+    solve_part_one_with_window(divan::black_box(include_str!("../input/day03.in")));
+}
+
 #[divan::bench]
 fn day_three_part_two() {
     solve_part_two(divan::black_box(include_str!("../input/day03.in")));
 }
+
+#[divan::bench]
+fn day_three_part_two_with_window() {
+    solve_part_two_with_window(divan::black_box(include_str!("../input/day03.in")));
+}
@@ -1,82 +1,174 @@
+use aoc_common::parse::ParseError;
 use core::iter::Peekable;
 use core::str::Chars;
 use itertools::Itertools;
 use rayon::prelude::*;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::fmt;
+
+/// What the characters of a schematic mean. The default is the puzzle's
+/// reading: `.` is blank, everything else that is not a digit is a symbol
+/// and a `*` next to exactly two numbers is a gear. Only the solvers in this
+/// module take rules, `day03_window` and `graph::Schematic` always read a
+/// schematic the puzzle's way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    /// Characters that are symbols, or `None` for any character that is not
+    /// a blank or a digit. Anything else in the schematic is an error.
+    pub symbols: Option<Vec<char>>,
+    pub blanks: Vec<char>,
+    /// Symbols that can be gears.
+    pub gears: Vec<char>,
+    /// How many numbers a gear has to be next to.
+    pub gear_neighbours: usize,
+    /// Read a `-` directly in front of a digit as the sign of the number
+    /// rather than as a symbol.
+    pub negative_numbers: bool,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules {
+            symbols: None,
+            blanks: vec!['.'],
+            gears: vec!['*'],
+            gear_neighbours: 2,
+            negative_numbers: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchematicError {
+    Parse(ParseError),
+    /// The answer does not fit in an `i64`.
+    Overflow,
+}
+
+impl fmt::Display for SchematicError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchematicError::Parse(e) => write!(f, "{}", e),
+            SchematicError::Overflow => write!(f, "answer does not fit in an i64"),
+        }
+    }
+}
+
+impl std::error::Error for SchematicError {}
+
+impl From<ParseError> for SchematicError {
+    fn from(e: ParseError) -> Self {
+        SchematicError::Parse(e)
+    }
+}
+
+impl Rules {
+    fn is_blank(&self, c: char) -> bool {
+        self.blanks.contains(&c)
+    }
+
+    fn is_symbol(&self, c: char) -> bool {
+        match &self.symbols {
+            Some(symbols) => symbols.contains(&c),
+            None => !c.is_ascii_digit() && !self.is_blank(c),
+        }
+    }
+}
 
 struct MaxtrixIterator<'a> {
     data: Peekable<Chars<'a>>,
+    rules: &'a Rules,
     row: usize,
     col: usize,
     digit_acc: String,
 }
 
-fn matrix_iterator(input: &str) -> MaxtrixIterator {
+fn matrix_iterator<'a>(input: &'a str, rules: &'a Rules) -> MaxtrixIterator<'a> {
     MaxtrixIterator {
         data: input.chars().peekable(),
+        rules,
         row: 0,
         col: 0,
         digit_acc: "".to_string(),
     }
 }
 
-impl Iterator for MaxtrixIterator<'_> {
-    type Item = MatrixElement;
+impl MaxtrixIterator<'_> {
+    fn error(&self, message: String) -> ParseError {
+        ParseError {
+            line: self.row + 1,
+            column: self.col + 1,
+            message,
+        }
+    }
+}
 
-    fn next(&mut self) -> Option<MatrixElement> {
-        let next = self.data.next();
-        next?;
+impl Iterator for MaxtrixIterator<'_> {
+    type Item = Result<MatrixElement, ParseError>;
 
-        match next.unwrap() {
-            '\n' => {
-                self.row += 1;
-                self.col = 0;
-                self.next()
-            }
-            c if c.is_ascii_digit() => {
-                self.digit_acc.push(c);
-                match self.data.peek() {
-                    Some(pc) if pc.is_ascii_digit() => {
+    fn next(&mut self) -> Option<Self::Item> {
+        // rows can have any length, positions only ever count from the start
+        // of their own row
+        loop {
+            let c = self.data.next()?;
+            let next_is_digit = self.data.peek().is_some_and(char::is_ascii_digit);
+            match c {
+                '\n' => {
+                    self.row += 1;
+                    self.col = 0;
+                }
+                '\r' if self.data.peek() == Some(&'\n') => {}
+                '-' if self.rules.negative_numbers
+                    && self.digit_acc.is_empty()
+                    && next_is_digit =>
+                {
+                    self.digit_acc.push(c);
+                    self.col += 1;
+                }
+                c if c.is_ascii_digit() => {
+                    self.digit_acc.push(c);
+                    if next_is_digit {
                         self.col += 1;
-                        self.next()
+                        continue;
                     }
-                    _ => {
-                        let digit_range = DigitRange {
-                            value: self
-                                .digit_acc
-                                .parse::<u32>()
-                                .expect("should be a string of digits only"),
+                    let low_x = self.col + 1 - self.digit_acc.len();
+                    let value = match self.digit_acc.parse::<i64>() {
+                        Ok(value) => value,
+                        Err(_) => {
+                            self.col = low_x;
+                            return Some(Err(self.error("number too large".to_string())));
+                        }
+                    };
+                    let digit_range = DigitRange {
+                        value,
+                        y: self.row,
+                        high_x: self.col,
+                        low_x,
+                    };
+                    self.col += 1;
+                    self.digit_acc.clear();
+                    return Some(Ok(MatrixElement::DigitRange(digit_range)));
+                }
+                c if self.rules.is_blank(c) => {
+                    self.col += 1;
+                }
+                c if self.rules.is_symbol(c) => {
+                    let symbol = Symbol {
+                        val: c,
+                        pos: Position {
+                            x: self.col,
                             y: self.row,
-                            high_x: self.col,
-                            low_x: self.col - (self.digit_acc.len() - 1),
-                        };
-                        self.col += 1;
-                        self.digit_acc.clear();
-                        Some(MatrixElement::DigitRange(digit_range))
-                    }
+                        },
+                    };
+                    self.col += 1;
+                    return Some(Ok(MatrixElement::Symbol(symbol)));
+                }
+                c => {
+                    return Some(Err(
+                        self.error(format!("'{}' is neither a symbol nor a blank", c))
+                    ));
                 }
-            }
-            c if c != '.' => {
-                let symbol = Symbol {
-                    val: c,
-                    pos: Position {
-                        x: self.col,
-                        y: self.row,
-                    },
-                };
-                self.col += 1;
-                Some(MatrixElement::Symbol(symbol))
-            }
-            '.' => {
-                self.col += 1;
-                self.next()
-            }
-            c => {
-                unreachable!(
-                    "at character: {}, but all characters should be ., digit, or newline",
-                    c
-                )
             }
         }
     }
@@ -129,7 +221,7 @@ impl Symbol {
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 struct DigitRange {
-    value: u32,
+    value: i64,
     low_x: usize,
     high_x: usize,
     y: usize,
@@ -147,18 +239,25 @@ struct Symbol {
     pos: Position,
 }
 
-pub fn solve_part_one(input: &str) -> u32 {
+pub fn solve_part_one(input: &str) -> i64 {
+    solve_part_one_with_rules(input, &Rules::default()).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Sum of the numbers next to a symbol under `rules`.
+pub fn solve_part_one_with_rules(input: &str, rules: &Rules) -> Result<i64, SchematicError> {
     let mut symbol_positions = HashSet::with_capacity(1000);
     let mut digit_ranges = Vec::with_capacity(1500);
 
-    matrix_iterator(input).for_each(|e| match e {
-        MatrixElement::Symbol(s) => {
-            symbol_positions.insert(s.pos);
-        }
-        MatrixElement::DigitRange(dr) => {
-            digit_ranges.push(dr);
+    for e in matrix_iterator(input, rules) {
+        match e? {
+            MatrixElement::Symbol(s) => {
+                symbol_positions.insert(s.pos);
+            }
+            MatrixElement::DigitRange(dr) => {
+                digit_ranges.push(dr);
+            }
         }
-    });
+    }
 
     digit_ranges
         .par_iter()
@@ -167,28 +266,37 @@ pub fn solve_part_one(input: &str) -> u32 {
                 .iter()
                 .any(|pos| symbol_positions.contains(pos))
         })
-        .map(|dr| dr.value)
-        .sum()
+        .map(|dr| Some(dr.value))
+        .try_reduce(|| 0, i64::checked_add)
+        .ok_or(SchematicError::Overflow)
 }
 
-pub fn solve_part_two(input: &str) -> u32 {
+pub fn solve_part_two(input: &str) -> i64 {
+    solve_part_two_with_rules(input, &Rules::default()).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Sum of the gear ratios under `rules`, where a gear ratio is the product
+/// of the numbers next to the gear.
+pub fn solve_part_two_with_rules(input: &str, rules: &Rules) -> Result<i64, SchematicError> {
     let mut digit_ranges = Vec::with_capacity(1000);
     let mut digit_positions = HashMap::with_capacity(1000);
     let mut gears = Vec::with_capacity(1000);
 
-    matrix_iterator(input).for_each(|e| match e {
-        MatrixElement::Symbol(s) => {
-            if s.val == '*' {
-                gears.push(s)
+    for e in matrix_iterator(input, rules) {
+        match e? {
+            MatrixElement::Symbol(s) => {
+                if rules.gears.contains(&s.val) {
+                    gears.push(s)
+                }
             }
-        }
-        MatrixElement::DigitRange(dr) => {
-            digit_ranges.push(dr);
-            for x in dr.low_x..dr.high_x + 1 {
-                digit_positions.insert(Position { x, y: dr.y }, dr);
+            MatrixElement::DigitRange(dr) => {
+                digit_ranges.push(dr);
+                for x in dr.low_x..dr.high_x + 1 {
+                    digit_positions.insert(Position { x, y: dr.y }, dr);
+                }
             }
         }
-    });
+    }
 
     gears
         .par_iter()
@@ -198,27 +306,22 @@ pub fn solve_part_two(input: &str) -> u32 {
                 .filter_map(|pos| digit_positions.get(pos))
                 .unique()
                 .map(|dr| dr.value)
-                .collect::<Vec<u32>>()
+                .collect::<Vec<i64>>()
+        })
+        .filter(|ds| ds.len() == rules.gear_neighbours)
+        .map(|ds| {
+            ds.iter()
+                .try_fold(1, |product, &d| i64::checked_mul(product, d))
         })
-        .filter(|ds| ds.len() == 2)
-        .map(|ds| ds.iter().product::<u32>())
-        .sum()
+        .try_reduce(|| 0, i64::checked_add)
+        .ok_or(SchematicError::Overflow)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = r#"467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598.."#;
+    const TEST_INPUT: &str = include_str!("../../examples/day03/example.in");
 
     #[test]
     fn test_solve_part_one() {
@@ -229,4 +332,83 @@ mod tests {
     fn test_solve_part_two() {
         assert_eq!(solve_part_two(TEST_INPUT), 467835);
     }
+
+    #[test]
+    fn test_default_rules() {
+        let rules = Rules::default();
+        assert_eq!(solve_part_one_with_rules(TEST_INPUT, &rules), Ok(4361));
+        assert_eq!(solve_part_two_with_rules(TEST_INPUT, &rules), Ok(467835));
+        // without negative numbers a `-` is just a symbol
+        assert_eq!(solve_part_one_with_rules("-12", &rules), Ok(12));
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let rules = Rules {
+            symbols: Some(vec!['#', '@']),
+            blanks: vec!['.', ' '],
+            gears: vec!['@'],
+            gear_neighbours: 3,
+            ..Rules::default()
+        };
+        let input = "1.2.3\n.#.@.\n4 5..";
+        assert_eq!(
+            solve_part_one_with_rules(input, &rules),
+            Ok(1 + 2 + 3 + 4 + 5)
+        );
+        // `@` touches 2, 3 and 5, `#` is not a gear
+        assert_eq!(solve_part_two_with_rules(input, &rules), Ok(2 * 3 * 5));
+
+        let err = solve_part_one_with_rules("1.2\n.*.", &rules).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 2: '*' is neither a symbol nor a blank"
+        );
+    }
+
+    #[test]
+    fn test_negative_numbers() {
+        let rules = Rules {
+            negative_numbers: true,
+            ..Rules::default()
+        };
+        // `-` only counts as a sign straight before a digit, so the `-` above
+        // 5 is a symbol and -4 touches nothing
+        let input = "-12*3.\n....-.\n.-4..5";
+        assert_eq!(solve_part_one_with_rules(input, &rules), Ok(-12 + 3 + 5));
+        assert_eq!(solve_part_two_with_rules(input, &rules), Ok(-12 * 3));
+        // the sign is part of the number's box
+        assert_eq!(solve_part_one_with_rules("#..\n.-7", &rules), Ok(-7));
+        assert_eq!(solve_part_one_with_rules("#...\n..-7", &rules), Ok(0));
+    }
+
+    #[test]
+    fn test_wide_answers() {
+        // neither fits in a u32, and the signs survive
+        let rules = Rules {
+            negative_numbers: true,
+            ..Rules::default()
+        };
+        let input = "-5000000000*3";
+        assert_eq!(solve_part_one_with_rules(input, &rules), Ok(-4999999997));
+        assert_eq!(solve_part_two_with_rules(input, &rules), Ok(-15000000000));
+        assert_eq!(solve_part_two("99999*99999"), 9999800001);
+
+        let input = "9223372036854775807*2";
+        assert_eq!(
+            solve_part_one_with_rules(input, &Rules::default()),
+            Err(SchematicError::Overflow)
+        );
+        assert_eq!(
+            solve_part_two_with_rules(input, &Rules::default()),
+            Err(SchematicError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_ragged_rows() {
+        let input = "467\r\n...*......\r\n..35\r\n\r\n1\n#";
+        assert_eq!(solve_part_one(input), 467 + 35 + 1);
+        assert_eq!(solve_part_two(input), 467 * 35);
+    }
 }
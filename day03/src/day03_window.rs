@@ -0,0 +1,204 @@
+use crate::day03::SchematicError;
+
+/// A row of the schematic together with the rows directly above and below
+/// it. Rows are slices of the input, so nothing is copied and at most three
+/// rows are looked at for any number or gear.
+struct Window<'a> {
+    above: &'a [u8],
+    row: &'a [u8],
+    below: &'a [u8],
+}
+
+impl<'a> Window<'a> {
+    fn rows(&self) -> [&'a [u8]; 3] {
+        [self.above, self.row, self.below]
+    }
+}
+
+/// Every row of `input` in its window, missing rows above the first and
+/// below the last row are empty.
+fn windows(input: &str) -> impl Iterator<Item = Window<'_>> {
+    let mut lines = input.lines().map(str::as_bytes);
+    let mut above: &[u8] = &[];
+    let mut row = lines.next();
+    let mut below = lines.next();
+    std::iter::from_fn(move || {
+        let current = row?;
+        let window = Window {
+            above,
+            row: current,
+            below: below.unwrap_or(&[]),
+        };
+        above = current;
+        row = below;
+        below = lines.next();
+        Some(window)
+    })
+}
+
+fn is_symbol(b: u8) -> bool {
+    b != b'.' && !b.is_ascii_digit()
+}
+
+/// Whether `row[x]` exists and is a digit. Rows can be ragged, anything past
+/// the end of a row is empty space.
+fn digit_at(row: &[u8], x: usize) -> bool {
+    row.get(x).is_some_and(u8::is_ascii_digit)
+}
+
+/// Columns `from..to` of `row`, clamped to the row.
+fn columns(row: &[u8], from: usize, to: usize) -> &[u8] {
+    &row[from.min(row.len())..to.min(row.len())]
+}
+
+/// Append the digit `b` to `n`, `Overflow` once it no longer fits.
+fn push_digit(n: i64, b: u8) -> Result<i64, SchematicError> {
+    n.checked_mul(10)
+        .and_then(|n| n.checked_add(i64::from(b - b'0')))
+        .ok_or(SchematicError::Overflow)
+}
+
+/// The number with a digit at `row[x]`.
+fn number_at(row: &[u8], x: usize) -> Result<i64, SchematicError> {
+    let start = (0..=x)
+        .rev()
+        .take_while(|&i| digit_at(row, i))
+        .last()
+        .unwrap_or(x);
+    row[start..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .try_fold(0, |n, &b| push_digit(n, b))
+}
+
+pub fn solve_part_one_with_window(input: &str) -> i64 {
+    try_solve_part_one_with_window(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Part one, with `SchematicError::Overflow` for a number or a sum that does
+/// not fit in an `i64` instead of a panic.
+pub fn try_solve_part_one_with_window(input: &str) -> Result<i64, SchematicError> {
+    let mut sum: i64 = 0;
+    for window in windows(input) {
+        let row = window.row;
+        let mut x = 0;
+        while x < row.len() {
+            if !row[x].is_ascii_digit() {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            let mut value = 0;
+            while x < row.len() && row[x].is_ascii_digit() {
+                value = push_digit(value, row[x])?;
+                x += 1;
+            }
+            // the box around the number, one column either side
+            let from = start.saturating_sub(1);
+            let touches_symbol = window
+                .rows()
+                .iter()
+                .any(|r| columns(r, from, x + 1).iter().any(|&b| is_symbol(b)));
+            if touches_symbol {
+                sum = sum.checked_add(value).ok_or(SchematicError::Overflow)?;
+            }
+        }
+    }
+    Ok(sum)
+}
+
+pub fn solve_part_two_with_window(input: &str) -> i64 {
+    try_solve_part_two_with_window(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Part two, with `SchematicError::Overflow` for a number, gear ratio or sum
+/// that does not fit in an `i64` instead of a panic.
+pub fn try_solve_part_two_with_window(input: &str) -> Result<i64, SchematicError> {
+    let mut sum: i64 = 0;
+    for window in windows(input) {
+        for (x, _) in window.row.iter().enumerate().filter(|(_, &b)| b == b'*') {
+            let from = x.saturating_sub(1);
+            let mut count = 0;
+            let mut numbers = [0; 2];
+            for row in window.rows() {
+                for col in from..=x + 1 {
+                    // count each number once, at its leftmost digit in the box
+                    let first_in_box = col == from || !digit_at(row, col - 1);
+                    if digit_at(row, col) && first_in_box {
+                        if let Some(n) = numbers.get_mut(count) {
+                            *n = number_at(row, col)?;
+                        }
+                        count += 1;
+                    }
+                }
+            }
+            if count == 2 {
+                sum = numbers[0]
+                    .checked_mul(numbers[1])
+                    .and_then(|ratio| sum.checked_add(ratio))
+                    .ok_or(SchematicError::Overflow)?;
+            }
+        }
+    }
+    Ok(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../examples/day03/example.in");
+
+    #[test]
+    fn test_solve_with_window() {
+        assert_eq!(solve_part_one_with_window(TEST_INPUT), 4361);
+        assert_eq!(solve_part_two_with_window(TEST_INPUT), 467835);
+    }
+
+    #[test]
+    fn test_edges() {
+        // numbers and gears on the first and last rows and columns
+        let input = "12*34\n.....\n5...#\n*...6\n7....";
+        assert_eq!(solve_part_one_with_window(input), 12 + 34 + 5 + 6 + 7);
+        assert_eq!(solve_part_two_with_window(input), 12 * 34 + 5 * 7);
+        // a number touching a gear twice is still one number
+        assert_eq!(solve_part_two_with_window("123\n.*.\n4.."), 123 * 4);
+        // ragged rows
+        assert_eq!(solve_part_one_with_window("1\n.#\n22.."), 1 + 22);
+    }
+
+    #[test]
+    fn test_crowded_gear() {
+        // a gear needs exactly two numbers, four is not a gear
+        let input = "999.999\n...*...\n999.999";
+        assert_eq!(solve_part_two_with_window(input), 0);
+        assert_eq!(solve_part_two_with_window("999.999\n...*..."), 999 * 999);
+    }
+
+    #[test]
+    fn test_overflow() {
+        assert_eq!(solve_part_two_with_window("99999*99999"), 9999800001);
+        let too_long = "99999999999999999999*1";
+        assert_eq!(
+            try_solve_part_one_with_window(too_long),
+            Err(SchematicError::Overflow)
+        );
+        assert_eq!(
+            try_solve_part_two_with_window(too_long),
+            Err(SchematicError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_matches_hashing_version() {
+        let input = include_str!("../input/day03.in");
+        assert_eq!(
+            solve_part_one_with_window(input),
+            crate::day03::solve_part_one(input)
+        );
+        assert_eq!(
+            solve_part_two_with_window(input),
+            crate::day03::solve_part_two(input)
+        );
+    }
+}
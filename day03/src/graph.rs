@@ -0,0 +1,290 @@
+//! A schematic as a bipartite graph: numbers on one side, symbols on the
+//! other, with an edge wherever a symbol is in the box around a number.
+//! Numbers and symbols are referred to by their index in
+//! [`Schematic::numbers`] and [`Schematic::symbols`].
+
+use crate::day03::SchematicError;
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number {
+    pub value: i64,
+    pub row: usize,
+    /// First column of the number.
+    pub start: usize,
+    /// One past the last column of the number.
+    pub end: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub kind: char,
+    pub row: usize,
+    pub col: usize,
+}
+
+/// A connected group of numbers and symbols that touch each other, directly
+/// or through other parts.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Component {
+    pub numbers: Vec<usize>,
+    pub symbols: Vec<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    /// Symbols adjacent to each number.
+    number_edges: Vec<Vec<usize>>,
+    /// Numbers adjacent to each symbol.
+    symbol_edges: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    /// Every number and symbol of `input` and which of them touch, or
+    /// `SchematicError::Overflow` for a number that does not fit in an `i64`.
+    pub fn parse(input: &str) -> Result<Schematic, SchematicError> {
+        let mut schematic = Schematic::default();
+        // symbol indices of every row, in column order
+        let mut symbols_by_row: Vec<Vec<usize>> = vec![];
+
+        for (row, line) in input.lines().enumerate() {
+            let mut row_symbols = vec![];
+            let mut number: Option<Number> = None;
+            for (col, c) in line.chars().enumerate() {
+                match c.to_digit(10) {
+                    Some(d) => {
+                        let n = number.get_or_insert(Number {
+                            value: 0,
+                            row,
+                            start: col,
+                            end: col,
+                        });
+                        n.value = n
+                            .value
+                            .checked_mul(10)
+                            .and_then(|value| value.checked_add(i64::from(d)))
+                            .ok_or(SchematicError::Overflow)?;
+                        n.end = col + 1;
+                        continue;
+                    }
+                    None if c == '.' => {}
+                    None => {
+                        row_symbols.push(schematic.symbols.len());
+                        schematic.symbols.push(Symbol { kind: c, row, col });
+                    }
+                }
+                schematic.numbers.extend(number.take());
+            }
+            schematic.numbers.extend(number.take());
+            symbols_by_row.push(row_symbols);
+        }
+
+        schematic.number_edges = vec![vec![]; schematic.numbers.len()];
+        schematic.symbol_edges = vec![vec![]; schematic.symbols.len()];
+        for (n, number) in schematic.numbers.iter().enumerate() {
+            let rows = number.row.saturating_sub(1)..=number.row + 1;
+            let cols = number.start.saturating_sub(1)..=number.end;
+            for row in rows.filter_map(|r| symbols_by_row.get(r)) {
+                for &s in row {
+                    if cols.contains(&schematic.symbols[s].col) {
+                        schematic.number_edges[n].push(s);
+                        schematic.symbol_edges[s].push(n);
+                    }
+                }
+            }
+        }
+        Ok(schematic)
+    }
+
+    /// Symbols touching number `n`.
+    pub fn symbols_of(&self, n: usize) -> &[usize] {
+        &self.number_edges[n]
+    }
+
+    /// Numbers touching symbol `s`.
+    pub fn numbers_of(&self, s: usize) -> &[usize] {
+        &self.symbol_edges[s]
+    }
+
+    /// Numbers touching at least one symbol, the part numbers of part one.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers_with_symbols(1)
+    }
+
+    /// Numbers touching at least one symbol of `kind`, each listed once.
+    pub fn numbers_adjacent_to(&self, kind: char) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(&self.number_edges)
+            .filter(move |(_, edges)| edges.iter().any(|&s| self.symbols[s].kind == kind))
+            .map(|(number, _)| number)
+    }
+
+    /// Symbols touching exactly `count` numbers, e.g. the gears of part two
+    /// are the `*` symbols with 2.
+    pub fn symbols_with_neighbours(&self, count: usize) -> impl Iterator<Item = &Symbol> {
+        self.symbols
+            .iter()
+            .zip(&self.symbol_edges)
+            .filter(move |(_, edges)| edges.len() == count)
+            .map(|(symbol, _)| symbol)
+    }
+
+    /// Numbers touching at least `count` symbols.
+    pub fn numbers_with_symbols(&self, count: usize) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(&self.number_edges)
+            .filter(move |(_, edges)| edges.len() >= count)
+            .map(|(number, _)| number)
+    }
+
+    /// Groups of parts connected by adjacency. Numbers and symbols that touch
+    /// nothing form components of their own.
+    pub fn components(&self) -> Vec<Component> {
+        let mut seen_numbers = vec![false; self.numbers.len()];
+        let mut seen_symbols = vec![false; self.symbols.len()];
+        let mut components = vec![];
+
+        // start a search from every unseen node, numbers first
+        let starts = (0..self.numbers.len())
+            .map(Node::Number)
+            .chain((0..self.symbols.len()).map(Node::Symbol));
+        for start in starts {
+            let seen = match start {
+                Node::Number(n) => &mut seen_numbers[n],
+                Node::Symbol(s) => &mut seen_symbols[s],
+            };
+            if *seen {
+                continue;
+            }
+            *seen = true;
+
+            let mut component = Component::default();
+            let mut queue = VecDeque::from([start]);
+            while let Some(node) = queue.pop_front() {
+                match node {
+                    Node::Number(n) => {
+                        component.numbers.push(n);
+                        for &s in &self.number_edges[n] {
+                            if !seen_symbols[s] {
+                                seen_symbols[s] = true;
+                                queue.push_back(Node::Symbol(s));
+                            }
+                        }
+                    }
+                    Node::Symbol(s) => {
+                        component.symbols.push(s);
+                        for &n in &self.symbol_edges[s] {
+                            if !seen_numbers[n] {
+                                seen_numbers[n] = true;
+                                queue.push_back(Node::Number(n));
+                            }
+                        }
+                    }
+                }
+            }
+            component.numbers.sort_unstable();
+            component.symbols.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Node {
+    Number(usize),
+    Symbol(usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../examples/day03/example.in");
+
+    #[test]
+    fn test_puzzle_answers() {
+        let schematic = Schematic::parse(TEST_INPUT).unwrap();
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(schematic.symbols.len(), 6);
+
+        let part_one: i64 = schematic.part_numbers().map(|n| n.value).sum();
+        assert_eq!(part_one, 4361);
+
+        let gears = schematic
+            .symbols
+            .iter()
+            .enumerate()
+            .filter(|(s, symbol)| symbol.kind == '*' && schematic.numbers_of(*s).len() == 2);
+        let part_two: i64 = gears
+            .map(|(s, _)| {
+                schematic
+                    .numbers_of(s)
+                    .iter()
+                    .map(|&n| schematic.numbers[n].value)
+                    .product::<i64>()
+            })
+            .sum();
+        assert_eq!(part_two, 467835);
+    }
+
+    #[test]
+    fn test_queries() {
+        let schematic = Schematic::parse(TEST_INPUT).unwrap();
+        let values = |numbers: Vec<&Number>| numbers.iter().map(|n| n.value).collect::<Vec<_>>();
+
+        assert_eq!(
+            values(schematic.numbers_adjacent_to('*').collect()),
+            [467, 35, 617, 755, 598]
+        );
+        assert_eq!(values(schematic.numbers_adjacent_to('$').collect()), [664]);
+        assert_eq!(values(schematic.numbers_adjacent_to('?').collect()), []);
+
+        let lonely: Vec<char> = schematic
+            .symbols_with_neighbours(1)
+            .map(|s| s.kind)
+            .collect();
+        assert_eq!(lonely, ['#', '*', '+', '$']);
+        assert_eq!(schematic.symbols_with_neighbours(2).count(), 2);
+
+        assert_eq!(schematic.numbers_with_symbols(2).count(), 0);
+        let shared = Schematic::parse("1.2\n*#.\n").unwrap();
+        assert_eq!(values(shared.numbers_with_symbols(2).collect()), [1]);
+        assert_eq!(shared.symbols_of(0), [0, 1]);
+    }
+
+    #[test]
+    fn test_components() {
+        let schematic = Schematic::parse("1*2..\n.....\n3#4.5\n..+..\n").unwrap();
+        let components = schematic.components();
+        let values = |c: &Component| {
+            c.numbers
+                .iter()
+                .map(|&n| schematic.numbers[n].value)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(components.len(), 3);
+        assert_eq!(values(&components[0]), [1, 2]);
+        assert_eq!(components[0].symbols, [0]);
+        // 3 and 4 share `#`, and 4 reaches `+` too
+        assert_eq!(values(&components[1]), [3, 4]);
+        assert_eq!(components[1].symbols, [1, 2]);
+        assert_eq!(values(&components[2]), [5]);
+        assert!(components[2].symbols.is_empty());
+    }
+
+    #[test]
+    fn test_overflow() {
+        let schematic = Schematic::parse("9223372036854775807*4294967296").unwrap();
+        assert_eq!(schematic.numbers[0].value, i64::MAX);
+        assert_eq!(schematic.numbers[1].value, 1 << 32);
+        assert_eq!(
+            Schematic::parse("...\n9223372036854775808*").unwrap_err(),
+            SchematicError::Overflow
+        );
+    }
+}
@@ -1 +1,3 @@
 pub mod day03;
+pub mod day03_window;
+pub mod graph;
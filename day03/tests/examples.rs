@@ -0,0 +1,38 @@
+//! Runs both day03 implementations against every example in `examples/day03`,
+//! and checks that both report answers that do not fit in an `i64`.
+
+macro_rules! implementation_examples {
+    ($module:ident, $try_part_one:expr, $try_part_two:expr) => {
+        mod $module {
+            use aoc_2023::day03::SchematicError;
+
+            fn solve_part_one(input: &str) -> i64 {
+                ($try_part_one)(input).unwrap_or_else(|e| panic!("{}", e))
+            }
+
+            fn solve_part_two(input: &str) -> i64 {
+                ($try_part_two)(input).unwrap_or_else(|e| panic!("{}", e))
+            }
+
+            include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+
+            #[test]
+            fn test_overflow() {
+                let input = "9223372036854775807*2";
+                assert_eq!(($try_part_one)(input), Err(SchematicError::Overflow));
+                assert_eq!(($try_part_two)(input), Err(SchematicError::Overflow));
+            }
+        }
+    };
+}
+
+implementation_examples!(
+    hashing,
+    |input| aoc_2023::day03::solve_part_one_with_rules(input, &Default::default()),
+    |input| aoc_2023::day03::solve_part_two_with_rules(input, &Default::default())
+);
+implementation_examples!(
+    window,
+    aoc_2023::day03_window::try_solve_part_one_with_window,
+    aoc_2023::day03_window::try_solve_part_two_with_window
+);
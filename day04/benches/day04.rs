@@ -1,4 +1,5 @@
 use aoc_2023::day04::*;
+use aoc_2023::number_set::NumberSet;
 
 fn main() {
     // Run registered benchmarks.
@@ -14,3 +15,80 @@ fn day_four_part_one() {
 fn day_four_part_two() {
     solve_part_two(divan::black_box(include_str!("../input/day04.in")));
 }
+
+/// A deterministic table of `cards` cards with 10 winning and `scratched`
+/// scratched numbers each, drawn from `0..range`, shaped like the puzzle
+/// input.
+fn generate(cards: usize, scratched: usize, range: u32) -> String {
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next = move || {
+        // xorshift64
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % range as u64) as u32
+    };
+    let mut input = String::new();
+    for id in 1..=cards {
+        let winning: Vec<String> = (0..10).map(|_| next().to_string()).collect();
+        let scratched: Vec<String> = (0..scratched).map(|_| next().to_string()).collect();
+        input += &format!(
+            "Card {}: {} | {}\n",
+            id,
+            winning.join(" "),
+            scratched.join(" ")
+        );
+    }
+    input
+}
+
+#[divan::bench(args = [1_000, 100_000])]
+fn generated_part_one(bencher: divan::Bencher, cards: usize) {
+    let input = generate(cards, 25, 100);
+    bencher.bench(|| solve_part_one(divan::black_box(&input)));
+}
+
+#[divan::bench(args = [1_000, 100_000])]
+fn generated_part_two(bencher: divan::Bencher, cards: usize) {
+    // few enough winners that the card count fits in a u64 on long tables
+    let input = generate(cards, 5, 100);
+    bencher.bench(|| solve_part_two(divan::black_box(&input)));
+}
+
+/// Parsed numbers of a generated table, to time matching on its own.
+fn generated_numbers(range: u32) -> Vec<(Vec<u32>, Vec<u32>)> {
+    generate(10_000, 25, range)
+        .lines()
+        .map(|line| {
+            let (_, numbers) = line.split_once(':').unwrap();
+            let (winning, scratched) = numbers.split_once('|').unwrap();
+            let parse = |s: &str| s.split_whitespace().map(|n| n.parse().unwrap()).collect();
+            (parse(winning), parse(scratched))
+        })
+        .collect()
+}
+
+#[divan::bench(args = [100, 10_000])]
+fn match_with_scan(bencher: divan::Bencher, range: u32) {
+    let cards = generated_numbers(range);
+    bencher.bench(|| {
+        divan::black_box(&cards)
+            .iter()
+            .map(|(winning, scratched)| scratched.iter().filter(|n| winning.contains(n)).count())
+            .sum::<usize>()
+    });
+}
+
+#[divan::bench(args = [100, 10_000])]
+fn match_with_bitset(bencher: divan::Bencher, range: u32) {
+    let cards: Vec<(NumberSet, NumberSet)> = generated_numbers(range)
+        .into_iter()
+        .map(|(w, s)| (w.into_iter().collect(), s.into_iter().collect()))
+        .collect();
+    bencher.bench(|| {
+        divan::black_box(&cards)
+            .iter()
+            .map(|(winning, scratched)| winning.intersection_len(scratched))
+            .sum::<u32>()
+    });
+}
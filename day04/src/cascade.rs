@@ -0,0 +1,167 @@
+//! Where the copies of part two come from. Cards are referred to by their
+//! index in table order, so the first card is 0.
+//!
+//! Winning copies happens in rounds: in round 1 every original card wins
+//! copies of the cards after it, in round 2 the copies won in round 1 win
+//! their own copies, and so on until a round wins nothing.
+
+use crate::day04::ScoreError;
+
+/// The copies won of one card.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CardCopies {
+    /// Copies won, not counting the original.
+    pub copies: u64,
+    /// The cards this card was won from, with how many copies each gave.
+    /// Every instance of an earlier card, original or copy, gives one.
+    pub won_from: Vec<(usize, u64)>,
+    /// The last round in which copies of this card were won, 0 if none
+    /// ever were.
+    pub last_round: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Cascade {
+    cards: Vec<CardCopies>,
+    rounds: usize,
+}
+
+impl Cascade {
+    /// The cascade for a table with `winners` winners on each card. As in
+    /// part two, copies past the end of the table are not won, and it fails
+    /// when a card ends up with more copies than fit in a `u64`.
+    pub fn new(winners: &[u32]) -> Result<Cascade, ScoreError> {
+        let mut cards = vec![CardCopies::default(); winners.len()];
+        for idx in 0..winners.len() {
+            let instances = cards[idx]
+                .copies
+                .checked_add(1)
+                .ok_or(ScoreError::TooManyCards)?;
+            // a copy won in round r wins its own copies in round r + 1
+            let round = cards[idx].last_round + 1;
+            let won = idx + 1..(idx + 1 + winners[idx] as usize).min(winners.len());
+            for card in &mut cards[won] {
+                card.copies = card
+                    .copies
+                    .checked_add(instances)
+                    .ok_or(ScoreError::TooManyCards)?;
+                card.won_from.push((idx, instances));
+                card.last_round = card.last_round.max(round);
+            }
+        }
+        let rounds = cards.iter().map(|c| c.last_round).max().unwrap_or(0);
+        Ok(Cascade { cards, rounds })
+    }
+
+    /// The copies of every card, in table order.
+    pub fn cards(&self) -> &[CardCopies] {
+        &self.cards
+    }
+
+    /// How many rounds it takes until no more copies are won.
+    pub fn rounds(&self) -> usize {
+        self.rounds
+    }
+
+    /// Originals and copies together, the answer to part two.
+    pub fn total_cards(&self) -> Result<u64, ScoreError> {
+        self.cards
+            .iter()
+            .try_fold(0u64, |total, c| total.checked_add(c.copies)?.checked_add(1))
+            .ok_or(ScoreError::TooManyCards)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeMap, VecDeque};
+
+    /// Play the cascade card by card: every instance on the queue wins a new
+    /// instance of each of the next cards, one round after its own.
+    fn simulate(winners: &[u32]) -> Cascade {
+        let mut won_from: Vec<BTreeMap<usize, u64>> = vec![BTreeMap::new(); winners.len()];
+        let mut cards = vec![CardCopies::default(); winners.len()];
+        let mut queue: VecDeque<(usize, usize)> = (0..winners.len()).map(|idx| (idx, 0)).collect();
+        while let Some((idx, round)) = queue.pop_front() {
+            let end = (idx + 1 + winners[idx] as usize).min(winners.len());
+            for won in idx + 1..end {
+                *won_from[won].entry(idx).or_default() += 1;
+                cards[won].copies += 1;
+                cards[won].last_round = cards[won].last_round.max(round + 1);
+                queue.push_back((won, round + 1));
+            }
+        }
+        for (card, sources) in cards.iter_mut().zip(won_from) {
+            card.won_from = sources.into_iter().collect();
+        }
+        let rounds = cards.iter().map(|c| c.last_round).max().unwrap_or(0);
+        Cascade { cards, rounds }
+    }
+
+    #[test]
+    fn test_example_cascade() {
+        let cascade = Cascade::new(&[4, 2, 2, 1, 0, 0]).unwrap();
+        let copies: Vec<u64> = cascade.cards().iter().map(|c| c.copies).collect();
+        assert_eq!(copies, [0, 1, 3, 7, 13, 0]);
+        assert_eq!(cascade.total_cards(), Ok(30));
+        // the third card is won once from the first card and twice from the
+        // second, its original and its copy
+        assert_eq!(cascade.cards()[2].won_from, [(0, 1), (1, 2)]);
+        // 0 -> 1 -> 2 -> 3 -> 4
+        assert_eq!(cascade.rounds(), 4);
+        assert_eq!(cascade.cards()[4].last_round, 4);
+        assert_eq!(cascade.cards()[5].last_round, 0);
+        assert_eq!(cascade, simulate(&[4, 2, 2, 1, 0, 0]));
+    }
+
+    #[test]
+    fn test_real_input() {
+        let input = include_str!("../input/day04.in");
+        let cards = crate::day04::Scratchcards::parse(input).unwrap();
+        let cascade = cards.cascade().unwrap();
+        assert_eq!(
+            cascade.total_cards(),
+            Ok(crate::day04::solve_part_two(input))
+        );
+        assert_eq!(cascade, simulate(cards.winners()));
+    }
+
+    #[test]
+    fn test_matches_simulation() {
+        assert_eq!(Cascade::new(&[]), Ok(simulate(&[])));
+        // small pseudo-random tables, including copies past the end
+        let mut state: u32 = 7;
+        for len in 1..12 {
+            for _ in 0..20 {
+                let winners: Vec<u32> = (0..len)
+                    .map(|_| {
+                        state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                        (state >> 16) % 4
+                    })
+                    .collect();
+                assert_eq!(
+                    Cascade::new(&winners),
+                    Ok(simulate(&winners)),
+                    "{:?}",
+                    winners
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_too_many_cards() {
+        // card n is won from every card before it, so it has 2^n instances
+        let table = |len: usize| -> Vec<u32> { (0..len).map(|idx| (len - idx) as u32).collect() };
+        let cascade = Cascade::new(&table(64)).unwrap();
+        assert_eq!(cascade.cards()[63].copies, (1 << 63) - 1);
+        assert_eq!(cascade.total_cards(), Ok(u64::MAX));
+        assert_eq!(Cascade::new(&table(65)), Err(ScoreError::TooManyCards));
+
+        // every count fits, only the sum does not
+        let winners: Vec<u32> = [0].into_iter().chain(table(64)).collect();
+        let cascade = Cascade::new(&winners).unwrap();
+        assert_eq!(cascade.total_cards(), Err(ScoreError::TooManyCards));
+    }
+}
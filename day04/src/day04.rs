@@ -1,121 +1,192 @@
-use core::panic;
+use crate::cascade::Cascade;
+use crate::number_set::NumberSet;
+use aoc_common::parse::{integers, numbered, parse_within, ParseError};
+use nom::character::complete::{char, space0};
+use nom::combinator::map;
+use nom::sequence::{pair, separated_pair};
 use rayon::prelude::*;
+use std::fmt;
 
-#[derive(Debug, Clone, Copy)]
-struct CardResult {
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Card {
     id: u32,
-    winners: u32,
+    winning: NumberSet,
+    scratched: NumberSet,
 }
 
-pub fn solve_part_one(input: &str) -> u32 {
-    input.par_lines().map(score_card).sum()
+impl Card {
+    /// How many scratched numbers are winning numbers. A number scratched
+    /// twice only counts once.
+    fn winners(&self) -> u32 {
+        self.winning.intersection_len(&self.scratched)
+    }
 }
 
-pub fn solve_part_two(input: &str) -> u32 {
-    let mut card_counts: [u32; 196] = [1; 196];
-    let mut num_winners: [u32; 196] = [0; 196];
-
-    let mut num_cards = 0;
-    input.lines().for_each(|card| {
-        let result = card_winners(card);
-        let idx = result.id as usize;
-        num_winners[idx - 1] = result.winners;
-        num_cards += 1;
-    });
-
-    num_winners
-        .iter()
-        .enumerate()
-        .for_each(|(idx, num_winners)| {
-            for i in 1..num_winners + 1 {
-                card_counts[idx + (i as usize)] += card_counts[idx]
-            }
-        });
-
-    card_counts[..num_cards].iter().sum()
+/// A table of scratchcards, reduced to the number of winners on every card
+/// in table order. Both parts only need that, so an input is parsed once
+/// and can answer either.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scratchcards {
+    winners: Vec<u32>,
 }
 
-fn get_score(num_of_winners: u32) -> u32 {
-    match num_of_winners {
-        0 => 0,
-        1 => 1,
-        2 => 2,
-        3 => 4,
-        4 => 8,
-        5 => 16,
-        6 => 32,
-        7 => 64,
-        8 => 128,
-        9 => 256,
-        10 => 512,
-        _ => panic!("never expected more than 10 winning numbers"),
+impl Scratchcards {
+    pub fn parse(input: &str) -> Result<Scratchcards, ParseError> {
+        let winners = input
+            .par_lines()
+            .map(|line| parse_card(input, line).map(|card| card.winners()))
+            .collect::<Result<Vec<u32>, ParseError>>()?;
+        Ok(Scratchcards { winners })
     }
-}
 
-fn score_card(card: &str) -> u32 {
-    let (_, numbers) = card.split_once(':').unwrap();
-    let (winners, scratched) = numbers.split_once('|').unwrap();
+    /// Winners on every card, in table order.
+    pub fn winners(&self) -> &[u32] {
+        &self.winners
+    }
 
-    let mut winning_numbers: [&str; 10] = [""; 10];
-    for (i, winner) in winners.split(' ').filter(|n| !n.is_empty()).enumerate() {
-        winning_numbers[i] = winner;
+    /// The total score of the cards, the answer to part one, or `None` if it
+    /// does not fit in a `u64`.
+    pub fn score(&self) -> Option<u64> {
+        self.winners
+            .iter()
+            .try_fold(0u64, |total, &w| total.checked_add(get_score(w)?))
     }
 
-    let mut winners = 0;
-    for scratch in scratched.split(' ').filter(|n| !n.is_empty()) {
-        if winning_numbers.contains(&scratch) {
-            winners += 1;
+    /// How many cards we end up with after winning copies, the answer to
+    /// part two.
+    pub fn total_cards(&self) -> Result<u64, ScoreError> {
+        card_counts(&self.winners)?
+            .into_iter()
+            .try_fold(0u64, |total, count| total.checked_add(count))
+            .ok_or(ScoreError::TooManyCards)
+    }
+
+    /// How the copies of part two were won, card by card.
+    pub fn cascade(&self) -> Result<Cascade, ScoreError> {
+        Cascade::new(&self.winners)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScoreError {
+    Parse(ParseError),
+    /// The total score does not fit in a `u64`, which takes a card with more
+    /// than 64 winners.
+    TooLarge,
+    /// The number of cards in part two does not fit in a `u64`.
+    TooManyCards,
+}
+
+impl fmt::Display for ScoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScoreError::Parse(e) => write!(f, "{}", e),
+            ScoreError::TooLarge => write!(f, "score does not fit in a u64"),
+            ScoreError::TooManyCards => write!(f, "card count does not fit in a u64"),
         }
     }
+}
+
+impl std::error::Error for ScoreError {}
+
+impl From<ParseError> for ScoreError {
+    fn from(e: ParseError) -> Self {
+        ScoreError::Parse(e)
+    }
+}
+
+pub fn solve_part_one(input: &str) -> u64 {
+    try_solve_part_one(input).unwrap_or_else(|e| panic!("{}", e))
+}
 
-    get_score(winners)
+/// Part one, failing instead of panicking when the score is too big.
+pub fn try_solve_part_one(input: &str) -> Result<u64, ScoreError> {
+    Scratchcards::parse(input)?
+        .score()
+        .ok_or(ScoreError::TooLarge)
 }
 
-fn card_winners(card: &str) -> CardResult {
-    let (game_info, numbers) = card.split_once(':').unwrap();
-    let game_number = game_info
-        .split_once(' ')
-        .expect("game info tuple to be split by space")
-        .1
-        .trim()
-        .parse::<u32>()
-        .expect("game info tuple to parse as number");
-    let (winners, scratched) = numbers.split_once('|').unwrap();
-
-    let mut winning_numbers: [&str; 10] = [""; 10];
-    for (i, winner) in winners.split(' ').filter(|n| !n.is_empty()).enumerate() {
-        winning_numbers[i] = winner;
-    }
-
-    let mut winners = 0;
-    for scratch in scratched.split(' ').filter(|n| !n.is_empty()) {
-        if winning_numbers.contains(&scratch) {
-            winners += 1;
+pub fn solve_part_two(input: &str) -> u64 {
+    try_solve_part_two(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Part two, failing instead of panicking when there are too many cards.
+pub fn try_solve_part_two(input: &str) -> Result<u64, ScoreError> {
+    Scratchcards::parse(input)?.total_cards()
+}
+
+/// How many of each card we end up with, given the number of winners on
+/// every card in table order. A card with `n` winners wins a copy of each of
+/// the next `n` cards. The puzzle promises that never runs past the end of
+/// the table, for other inputs the copies that would are not won.
+fn card_counts(num_winners: &[u32]) -> Result<Vec<u64>, ScoreError> {
+    let mut card_counts = vec![1u64; num_winners.len()];
+    for (idx, &winners) in num_winners.iter().enumerate() {
+        let won = idx + 1..(idx + 1 + winners as usize).min(num_winners.len());
+        for i in won {
+            card_counts[i] = card_counts[i]
+                .checked_add(card_counts[idx])
+                .ok_or(ScoreError::TooManyCards)?;
         }
     }
+    Ok(card_counts)
+}
 
-    CardResult {
-        id: game_number,
-        winners,
+/// The score of a card with `winners` winning numbers: 1 for the first and
+/// doubled for every one after it, so `2^(winners - 1)`. `None` from 65
+/// winners on, where that no longer fits in a `u64`.
+fn get_score(winners: u32) -> Option<u64> {
+    match winners {
+        0 => Some(0),
+        n => 1u64.checked_shl(n - 1),
     }
 }
 
+/// Parse a `Card N: winning numbers | scratched numbers` line. `line` must be
+/// a slice of `source` so that errors point at the right line of the input.
+fn parse_card<'a>(source: &'a str, line: &'a str) -> Result<Card, ParseError> {
+    let numbers = separated_pair(integers, pair(space0, char('|')), integers);
+    let card = map(
+        pair(numbered("Card"), numbers),
+        |(id, (winning, scratched)): (u32, (Vec<u32>, Vec<u32>))| Card {
+            id,
+            winning: winning.into_iter().collect(),
+            scratched: scratched.into_iter().collect(),
+        },
+    );
+    parse_within(source, line, card)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     const TEST_LINE: &str = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53"#;
 
-    const TEST_INPUT: &str = r#"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
-Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
-Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
-Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
-Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
-Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
+    const TEST_INPUT: &str = include_str!("../../examples/day04/example.in");
 
     #[test]
     fn test_score_card() {
-        assert_eq!(score_card(TEST_LINE), 8);
+        let card = parse_card(TEST_LINE, TEST_LINE).unwrap();
+        assert_eq!(card.winners(), 4);
+        assert_eq!(get_score(card.winners()), Some(8));
+    }
+
+    #[test]
+    fn test_parse_card() {
+        assert_eq!(
+            parse_card(TEST_LINE, TEST_LINE),
+            Ok(Card {
+                id: 1,
+                winning: [41, 48, 83, 86, 17].into_iter().collect(),
+                scratched: [83, 86, 6, 31, 17, 9, 48, 53].into_iter().collect(),
+            })
+        );
+
+        let input = "Card 1: 1 2 | 3\nCard 2: 1 2 3";
+        let (_, second) = input.split_once('\n').unwrap();
+        let err = parse_card(input, second).unwrap_err();
+        assert_eq!((err.line, err.column), (2, 14));
     }
 
     #[test]
@@ -127,4 +198,110 @@ Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11"#;
     fn test_solve_part_two() {
         assert_eq!(solve_part_two(TEST_INPUT), 30);
     }
+
+    #[test]
+    fn test_scratchcards() {
+        let cards = Scratchcards::parse(TEST_INPUT).unwrap();
+        assert_eq!(cards.winners(), [4, 2, 2, 1, 0, 0]);
+        assert_eq!((cards.score(), cards.total_cards()), (Some(13), Ok(30)));
+        assert_eq!(cards.cascade().and_then(|c| c.total_cards()), Ok(30));
+
+        let err = Scratchcards::parse("Card 1: 1 | 1\nCard 2: 1 x | 2").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 11));
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let input = "Card 1: 5 127 128 999 | 999 128 4 127\nCard 2: 1000 | 1000";
+        assert_eq!(solve_part_one(input), 4 + 1);
+        assert_eq!(solve_part_two(input), 1 + 2);
+    }
+
+    #[test]
+    fn test_get_score() {
+        let scores: Vec<Option<u64>> = (0..=5).map(get_score).collect();
+        assert_eq!(scores, [0, 1, 2, 4, 8, 16].map(Some));
+        assert_eq!(get_score(11), Some(1024));
+        assert_eq!(get_score(64), Some(1 << 63));
+        assert_eq!(get_score(65), None);
+        assert_eq!(get_score(u32::MAX), None);
+    }
+
+    #[test]
+    fn test_score_too_large() {
+        let numbers: Vec<String> = (1..=65).map(|n| n.to_string()).collect();
+        let numbers = numbers.join(" ");
+        let input = format!("Card 1: {} | {}", numbers, numbers);
+        assert_eq!(try_solve_part_one(&input), Err(ScoreError::TooLarge));
+        // two cards that fit on their own but not together
+        let numbers: Vec<String> = (1..=64).map(|n| n.to_string()).collect();
+        let card = format!("{} | {}", numbers.join(" "), numbers.join(" "));
+        let input = format!("Card 1: {}\nCard 2: {}", card, card);
+        assert_eq!(try_solve_part_one(&input), Err(ScoreError::TooLarge));
+        assert_eq!(try_solve_part_one(TEST_INPUT), Ok(13));
+        assert!(matches!(
+            try_solve_part_one("Card 1: x"),
+            Err(ScoreError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn test_many_cards_and_winners() {
+        // more than 196 cards and more than 10 winners on a card
+        let card = |id: usize, winners: usize| {
+            let winning: Vec<String> = (1..=20).map(|n| n.to_string()).collect();
+            let scratched: Vec<String> = (1..=winners)
+                .chain(50..70 - winners)
+                .map(|n| n.to_string())
+                .collect();
+            format!(
+                "Card {}: {} | {}",
+                id,
+                winning.join(" "),
+                scratched.join(" ")
+            )
+        };
+        let input: Vec<String> = (1..=300)
+            .map(|id| card(id, if id == 1 { 15 } else { 0 }))
+            .collect();
+        let input = input.join("\n");
+        assert_eq!(solve_part_one(&input), 1 << 14);
+        assert_eq!(solve_part_two(&input), 300 + 15);
+    }
+
+    #[test]
+    fn test_copies_past_the_end() {
+        // the last cards win copies of cards that are not on the table
+        assert_eq!(card_counts(&[1, 2, 3]), Ok(vec![1, 2, 3]));
+        assert_eq!(card_counts(&[5]), Ok(vec![1]));
+        assert_eq!(card_counts(&[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_too_many_cards() {
+        // every card wins a copy of all the cards after it, so card n ends
+        // up with 2^n instances
+        let table = |len: usize| -> Vec<u32> { (0..len).map(|idx| (len - idx) as u32).collect() };
+        let cases = [
+            (table(64), Ok(u64::MAX)),
+            (table(65), Err(ScoreError::TooManyCards)),
+            (table(100), Err(ScoreError::TooManyCards)),
+        ];
+        for (winners, expected) in cases {
+            let cards = Scratchcards { winners };
+            assert_eq!(cards.total_cards(), expected, "{}", cards.winners.len());
+        }
+        // every count fits, only the sum does not
+        let winners: Vec<u32> = [0].into_iter().chain(table(64)).collect();
+        assert_eq!(card_counts(&winners).map(|c| c[64]), Ok(1 << 63));
+        assert_eq!(
+            Scratchcards { winners }.total_cards(),
+            Err(ScoreError::TooManyCards)
+        );
+        assert!(matches!(
+            try_solve_part_two("Card 1: x"),
+            Err(ScoreError::Parse(_))
+        ));
+        assert_eq!(try_solve_part_two(TEST_INPUT), Ok(30));
+    }
 }
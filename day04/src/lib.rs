@@ -1 +1,3 @@
+pub mod cascade;
 pub mod day04;
+pub mod number_set;
@@ -0,0 +1,116 @@
+/// Numbers below this bound are bits of a single `u128`.
+const DENSE_LIMIT: u32 = 128;
+
+/// A set of numbers stored as a bitset. Numbers below 128, which covers the
+/// puzzle's two digit numbers, live in a single `u128` so that matching two
+/// cards is one `and` and a `count_ones`. Larger numbers are kept sorted in
+/// `sparse` and matched by walking both lists. A card only has a few dozen
+/// numbers, so spilling into more bitset words instead costs far more memory
+/// than it saves, and was twice as slow as a plain scan on wide ranges.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NumberSet {
+    low: u128,
+    sparse: Vec<u32>,
+}
+
+impl NumberSet {
+    pub fn new() -> NumberSet {
+        NumberSet::default()
+    }
+
+    pub fn insert(&mut self, n: u32) {
+        if n >= DENSE_LIMIT {
+            if let Err(idx) = self.sparse.binary_search(&n) {
+                self.sparse.insert(idx, n);
+            }
+            return;
+        }
+        self.low |= 1 << n;
+    }
+
+    pub fn contains(&self, n: u32) -> bool {
+        if n >= DENSE_LIMIT {
+            return self.sparse.binary_search(&n).is_ok();
+        }
+        self.low & (1 << n) != 0
+    }
+
+    pub fn len(&self) -> u32 {
+        self.low.count_ones() + self.sparse.len() as u32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.low == 0 && self.sparse.is_empty()
+    }
+
+    /// Number of numbers in both sets.
+    pub fn intersection_len(&self, other: &NumberSet) -> u32 {
+        let dense = (self.low & other.low).count_ones();
+        // both lists are sorted, so walk them side by side, stepping past
+        // the smaller number (or both) without branching on the comparison
+        let (a, b) = (&self.sparse, &other.sparse);
+        let (mut i, mut j, mut sparse) = (0, 0, 0);
+        while i < a.len() && j < b.len() {
+            let (x, y) = (a[i], b[j]);
+            sparse += u32::from(x == y);
+            i += usize::from(x <= y);
+            j += usize::from(y <= x);
+        }
+        dense + sparse
+    }
+}
+
+impl FromIterator<u32> for NumberSet {
+    fn from_iter<I: IntoIterator<Item = u32>>(iter: I) -> NumberSet {
+        let mut set = NumberSet::new();
+        for n in iter {
+            set.insert(n);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_set() {
+        let a: NumberSet = [41, 48, 83, 86, 17].into_iter().collect();
+        let b: NumberSet = [83, 86, 6, 31, 17, 9, 48, 53].into_iter().collect();
+        assert_eq!((a.len(), b.len()), (5, 8));
+        assert_eq!(a.intersection_len(&b), 4);
+        assert!(a.contains(41) && !a.contains(42));
+        assert!(NumberSet::new().is_empty());
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let a: NumberSet = [0, 127, 128, 1000, 5000].into_iter().collect();
+        let b: NumberSet = [127, 1000, 4999].into_iter().collect();
+        assert_eq!(a.len(), 5);
+        assert_eq!(a.intersection_len(&b), 2);
+        assert_eq!(b.intersection_len(&a), 2);
+        assert!(a.contains(5000) && !a.contains(129) && !b.contains(5000));
+        // duplicates are one number
+        let c: NumberSet = [3, 3, 300, 300].into_iter().collect();
+        assert_eq!(c.len(), 2);
+    }
+
+    #[test]
+    fn test_huge_numbers() {
+        // these would need a bitset of hundreds of megabytes
+        let a: NumberSet = [4_000_000_000, 7, u32::MAX, 9000, DENSE_LIMIT]
+            .into_iter()
+            .collect();
+        let b: NumberSet = [u32::MAX, 4_000_000_000, 4_000_000_001, 7]
+            .into_iter()
+            .collect();
+        assert_eq!(a.sparse.len(), 4);
+        assert_eq!(a.len(), 5);
+        assert_eq!(a.intersection_len(&b), 3);
+        assert_eq!(b.intersection_len(&a), 3);
+        assert!(a.contains(u32::MAX) && a.contains(DENSE_LIMIT) && !a.contains(4_000_000_001));
+        assert!(!NumberSet::from_iter([u32::MAX]).is_empty());
+    }
+}
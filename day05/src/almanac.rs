@@ -0,0 +1,310 @@
+//! The almanac as a chain of named categories. Every `X-to-Y map:` section
+//! is an edge from category `X` to category `Y`, and together the sections
+//! must form a single chain, in whatever order they appear in the input.
+
+use crate::day05::{parse_rules, SeedMap};
+use aoc_common::parse::{integers, labelled, parse_within, sections, ParseError};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlmanacError {
+    Parse(ParseError),
+    /// Two maps leave the same category, or two lead into it.
+    Branch(String),
+    /// Some maps cannot be reached by following the chain from its start,
+    /// because they form a second chain or a cycle.
+    Disconnected(String),
+    UnknownCategory(String),
+    /// `to` comes before `from` in the chain.
+    NoPath {
+        from: String,
+        to: String,
+    },
+    /// There is no seed to find a location for.
+    NoSeeds,
+    /// The seeds are read as `start len` pairs, but one is missing its
+    /// length.
+    OddSeeds,
+    /// The seed range with this start runs past the largest value.
+    SeedRangeOverflow(u64),
+}
+
+impl fmt::Display for AlmanacError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AlmanacError::Parse(e) => write!(f, "{}", e),
+            AlmanacError::Branch(c) => write!(f, "category {} is in more than one chain", c),
+            AlmanacError::Disconnected(c) => {
+                write!(f, "category {} is not connected to the chain", c)
+            }
+            AlmanacError::UnknownCategory(c) => write!(f, "unknown category {}", c),
+            AlmanacError::NoPath { from, to } => write!(f, "no map from {} to {}", from, to),
+            AlmanacError::NoSeeds => write!(f, "there are no seeds"),
+            AlmanacError::OddSeeds => write!(f, "the last seed range has no length"),
+            AlmanacError::SeedRangeOverflow(start) => {
+                write!(f, "seed range from {} runs past the largest value", start)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AlmanacError {}
+
+impl From<ParseError> for AlmanacError {
+    fn from(e: ParseError) -> Self {
+        AlmanacError::Parse(e)
+    }
+}
+
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<u64>,
+    /// Categories in chain order, e.g. `seed`, `soil`, ..., `location`.
+    categories: Vec<String>,
+    /// `maps[i]` goes from `categories[i]` to `categories[i + 1]`.
+    maps: Vec<SeedMap>,
+}
+
+impl Almanac {
+    pub fn parse(input: &str) -> Result<Almanac, AlmanacError> {
+        let mut sections = sections(input);
+        let seeds = match sections.next() {
+            Some(section) => parse_within(input, section, labelled("seeds", integers))?,
+            None => return Err(ParseError::at(input, input, "expected seeds").into()),
+        };
+
+        // every map keyed by the category it starts from
+        let mut edges: HashMap<&str, (&str, SeedMap)> = HashMap::new();
+        let mut targets: HashMap<&str, &str> = HashMap::new();
+        for section in sections {
+            let (header, body) = section.split_once('\n').unwrap_or((section, ""));
+            let (from, to) = header
+                .trim_end()
+                .strip_suffix(" map:")
+                .and_then(|names| names.split_once("-to-"))
+                .filter(|(from, to)| !from.is_empty() && !to.is_empty())
+                .ok_or_else(|| ParseError::at(input, header, "expected X-to-Y map:"))?;
+            let map = parse_rules(input, body)?;
+            if edges.insert(from, (to, map)).is_some() {
+                return Err(AlmanacError::Branch(from.to_string()));
+            }
+            if targets.insert(to, from).is_some() {
+                return Err(AlmanacError::Branch(to.to_string()));
+            }
+        }
+
+        let mut categories = vec![];
+        let mut maps = vec![];
+        // the chain starts at the only category no map leads into
+        let mut starts = edges.keys().filter(|c| !targets.contains_key(*c));
+        if let Some(&start) = starts.next() {
+            if let Some(&other) = starts.next() {
+                return Err(AlmanacError::Disconnected(start.max(other).to_string()));
+            }
+            categories.push(start.to_string());
+            let mut category = start;
+            while let Some((to, map)) = edges.remove(category) {
+                categories.push(to.to_string());
+                maps.push(map);
+                category = to;
+            }
+        }
+        // whatever is left over is a cycle, or all of it is when there is
+        // no start
+        if let Some(&left) = edges.keys().min() {
+            return Err(AlmanacError::Disconnected(left.to_string()));
+        }
+
+        Ok(Almanac {
+            seeds,
+            categories,
+            maps,
+        })
+    }
+
+    /// The seeds read as `start len` pairs, as part two does.
+    pub fn seed_ranges(&self) -> Result<Vec<Range<u64>>, AlmanacError> {
+        if !self.seeds.len().is_multiple_of(2) {
+            return Err(AlmanacError::OddSeeds);
+        }
+        self.seeds
+            .chunks_exact(2)
+            .map(|pair| {
+                let (start, len) = (pair[0], pair[1]);
+                let end = start.checked_add(len);
+                end.map(|end| start..end)
+                    .ok_or(AlmanacError::SeedRangeOverflow(start))
+            })
+            .collect()
+    }
+
+    /// Categories in chain order, empty if the almanac has no maps.
+    pub fn categories(&self) -> &[String] {
+        &self.categories
+    }
+
+    /// The maps in chain order, from the first category to the last.
+    pub fn maps(&self) -> &[SeedMap] {
+        &self.maps
+    }
+
+    pub fn into_maps(self) -> Vec<SeedMap> {
+        self.maps
+    }
+
+    fn position(&self, category: &str) -> Result<usize, AlmanacError> {
+        self.categories
+            .iter()
+            .position(|c| c == category)
+            .ok_or_else(|| AlmanacError::UnknownCategory(category.to_string()))
+    }
+
+    /// The maps to go through from `from` to `to`, or from `to` to `from`
+    /// if `backwards`.
+    fn path(&self, from: &str, to: &str, backwards: bool) -> Result<&[SeedMap], AlmanacError> {
+        let (start, end) = (self.position(from)?, self.position(to)?);
+        let (start, end) = if backwards {
+            (end, start)
+        } else {
+            (start, end)
+        };
+        if start > end {
+            return Err(AlmanacError::NoPath {
+                from: from.to_string(),
+                to: to.to_string(),
+            });
+        }
+        Ok(&self.maps[start..end])
+    }
+
+    /// Translate `value` from category `from` to a later category `to`.
+    pub fn translate(&self, value: u64, from: &str, to: &str) -> Result<u64, AlmanacError> {
+        let maps = self.path(from, to, false)?;
+        Ok(maps.iter().fold(value, |v, map| map.translate(v)))
+    }
+
+    /// Every value of the earlier category `to` that translates to `value`
+    /// of category `from`, e.g. the seeds that end up at a location.
+    pub fn translate_back(
+        &self,
+        value: u64,
+        from: &str,
+        to: &str,
+    ) -> Result<Vec<u64>, AlmanacError> {
+        let maps = self.path(from, to, true)?;
+        Ok(SeedMap::compose_all(maps).translate_back(value))
+    }
+
+    /// A value of the first category, translated to the last.
+    pub fn translate_all(&self, value: u64) -> u64 {
+        self.maps.iter().fold(value, |v, map| map.translate(v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_INPUT: &str = include_str!("../../examples/day05/example.in");
+
+    #[test]
+    fn test_categories() {
+        let almanac = Almanac::parse(TEST_INPUT).unwrap();
+        assert_eq!(
+            almanac.categories(),
+            [
+                "seed",
+                "soil",
+                "fertilizer",
+                "water",
+                "light",
+                "temperature",
+                "humidity",
+                "location"
+            ]
+        );
+        assert_eq!(almanac.translate(79, "seed", "soil"), Ok(81));
+        assert_eq!(almanac.translate(79, "seed", "location"), Ok(82));
+        assert_eq!(almanac.translate(81, "soil", "location"), Ok(82));
+        assert_eq!(almanac.translate(5, "water", "water"), Ok(5));
+        assert_eq!(almanac.translate_back(82, "location", "seed"), Ok(vec![79]));
+        assert_eq!(
+            almanac.translate(82, "location", "seed"),
+            Err(AlmanacError::NoPath {
+                from: "location".to_string(),
+                to: "seed".to_string()
+            })
+        );
+        assert_eq!(
+            almanac.translate(1, "seed", "moon"),
+            Err(AlmanacError::UnknownCategory("moon".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_reordered_and_fewer_stages() {
+        let input = "seeds: 1 5\n\nb-to-c map:\n10 2 1\n\na-to-b map:\n2 1 1\n";
+        let almanac = Almanac::parse(input).unwrap();
+        assert_eq!(almanac.categories(), ["a", "b", "c"]);
+        assert_eq!(almanac.translate_all(1), 10);
+        assert_eq!(almanac.translate_all(5), 5);
+
+        let almanac = Almanac::parse("seeds: 3 4\n").unwrap();
+        assert!(almanac.categories().is_empty());
+        assert_eq!(almanac.translate_all(3), 3);
+    }
+
+    #[test]
+    fn test_invalid_chains() {
+        let branch = "seeds: 1\n\na-to-b map:\n1 1 1\n\na-to-c map:\n1 1 1\n";
+        assert_eq!(
+            Almanac::parse(branch).unwrap_err(),
+            AlmanacError::Branch("a".to_string())
+        );
+        let two_chains = "seeds: 1\n\na-to-b map:\n1 1 1\n\nc-to-d map:\n1 1 1\n";
+        assert_eq!(
+            Almanac::parse(two_chains).unwrap_err(),
+            AlmanacError::Disconnected("c".to_string())
+        );
+        let cycle = "seeds: 1\n\na-to-b map:\n1 1 1\n\nx-to-y map:\n1 1 1\n\ny-to-x map:\n1 1 1\n";
+        assert_eq!(
+            Almanac::parse(cycle).unwrap_err(),
+            AlmanacError::Disconnected("x".to_string())
+        );
+
+        let err = Almanac::parse("seeds: 1\n\na-to-b:\n1 1 1\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 1: expected X-to-Y map:");
+        let err = Almanac::parse("seeds: 1\n\na-to-b map:\n1 x 1\n").unwrap_err();
+        assert!(matches!(
+            err,
+            AlmanacError::Parse(ParseError { line: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_seed_ranges() {
+        let almanac = Almanac::parse(TEST_INPUT).unwrap();
+        assert_eq!(almanac.seed_ranges(), Ok(vec![79..93, 55..68]));
+        let almanac = Almanac::parse("seeds: 1 2 3\n").unwrap();
+        assert_eq!(almanac.seed_ranges(), Err(AlmanacError::OddSeeds));
+        let almanac = Almanac::parse("seeds: 18446744073709551615 1\n").unwrap();
+        assert_eq!(
+            almanac.seed_ranges(),
+            Err(AlmanacError::SeedRangeOverflow(u64::MAX))
+        );
+        let almanac = Almanac::parse("seeds: 5 0 7 1\n").unwrap();
+        assert_eq!(almanac.seed_ranges(), Ok(vec![5..5, 7..8]));
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        // the position counts from the start of the input, not of the map
+        let input = "seeds: 1\n\na-to-b map:\n1 1 1\n\nb-to-c map:\n1 1 1\n2 2 y\n";
+        match Almanac::parse(input).unwrap_err() {
+            AlmanacError::Parse(e) => assert_eq!((e.line, e.column), (8, 5)),
+            e => panic!("expected a parse error, got {:?}", e),
+        }
+    }
+}
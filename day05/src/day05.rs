@@ -1,22 +1,172 @@
+use crate::almanac::{Almanac, AlmanacError};
+use aoc_common::parse::{integers, parse_within, ParseError};
 use itertools::Itertools;
-use rayon::prelude::*;
-use std::cmp::max;
+use std::cmp::{max, min};
+use std::fmt;
 use std::ops::Range;
 
+/// Two rules of a map cover some of the same values, so a value could be
+/// translated two ways. The rules are given by their position in the list
+/// the map was built from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct OverlapError {
+    first: usize,
+    second: usize,
+}
+
+impl fmt::Display for OverlapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rules {} and {} overlap", self.first, self.second)
+    }
+}
+
+impl std::error::Error for OverlapError {}
+
+/// The rules of one map, sorted by where their input starts. No two rules
+/// overlap, so there is only ever one rule for a value.
 #[derive(Debug)]
 pub struct SeedMap {
     rules: Vec<MapRule>,
 }
 
 impl SeedMap {
-    fn translate(&self, s: u64) -> u64 {
-        for r in &self.rules {
-            if s >= r.in_start && s < r.in_start + r.len {
-                let offset = s - r.in_start;
-                return r.out_start + offset;
+    /// A map from rules in any order, failing if two of them overlap. Empty
+    /// rules cover nothing and are dropped.
+    pub(crate) fn new(rules: Vec<MapRule>) -> Result<SeedMap, OverlapError> {
+        let mut indexed: Vec<(usize, MapRule)> = rules
+            .into_iter()
+            .enumerate()
+            .filter(|(_, r)| r.len > 0)
+            .collect();
+        indexed.sort_by_key(|(_, r)| r.in_start);
+        // sorted, a rule can only overlap the one right before it
+        if let Some(w) = indexed
+            .windows(2)
+            .find(|w| w[0].1.in_end() > w[1].1.in_start)
+        {
+            return Err(OverlapError {
+                first: w[0].0.min(w[1].0),
+                second: w[0].0.max(w[1].0),
+            });
+        }
+        Ok(SeedMap {
+            rules: indexed.into_iter().map(|(_, r)| r).collect(),
+        })
+    }
+
+    /// The rule covering `s`, if any.
+    fn rule_for(&self, s: u64) -> Option<&MapRule> {
+        // the last rule starting at or before `s` is the only one that can
+        // contain it
+        let i = self.rules.partition_point(|r| r.in_start <= s);
+        self.rules[..i].last().filter(|r| s < r.in_end())
+    }
+
+    /// Map a value from this map's source category to its destination
+    /// category, values not covered by any rule map to themselves.
+    pub fn translate(&self, s: u64) -> u64 {
+        match self.rule_for(s) {
+            Some(r) => r.out_start + (s - r.in_start),
+            None => s,
+        }
+    }
+
+    /// Map every value in `ranges` at once. Ranges are split wherever a rule
+    /// starts or ends, so that each piece is either shifted by one rule or
+    /// not covered by any and left as it is. The pieces come back unsorted
+    /// and may overlap when two values map to the same place.
+    pub fn translate_ranges(&self, ranges: &[Range<u64>]) -> Vec<Range<u64>> {
+        let mut translated = vec![];
+        for range in ranges {
+            let mut start = range.start;
+            // the first rule that ends after `start`
+            let mut i = self.rules.partition_point(|r| r.in_end() <= start);
+            while start < range.end {
+                match self.rules.get(i) {
+                    Some(r) if r.in_start < range.end => {
+                        if start < r.in_start {
+                            translated.push(start..r.in_start);
+                            start = r.in_start;
+                        }
+                        let end = range.end.min(r.in_end());
+                        let piece = r.restrict(start, end);
+                        translated.push(piece.out_start..piece.out_end());
+                        start = end;
+                        i += 1;
+                    }
+                    _ => {
+                        translated.push(start..range.end);
+                        start = range.end;
+                    }
+                }
             }
         }
-        s
+        translated
+    }
+
+    /// The rules with identity rules filling the gaps between them, so that
+    /// every value from 0 up has exactly one rule.
+    fn pieces(&self) -> Vec<MapRule> {
+        let mut filled = Vec::with_capacity(self.rules.len() * 2 + 1);
+        let mut next = 0;
+        for &r in &self.rules {
+            if next < r.in_start {
+                filled.push(MapRule::identity(next, r.in_start));
+            }
+            next = r.in_end();
+            filled.push(r);
+        }
+        if next < u64::MAX {
+            filled.push(MapRule::identity(next, u64::MAX));
+        }
+        filled
+    }
+
+    /// This map followed by `next`, as a single map whose rules are sorted
+    /// and do not overlap.
+    pub fn compose(&self, next: &SeedMap) -> SeedMap {
+        let next_pieces = next.pieces();
+        let mut rules = vec![];
+        for piece in self.pieces() {
+            let mut rest = vec![piece];
+            for &n in &next_pieces {
+                rest = rest
+                    .into_iter()
+                    .flat_map(|r| {
+                        let (both, rest) = r.merge(n);
+                        rules.extend(both);
+                        rest
+                    })
+                    .collect();
+                if rest.is_empty() {
+                    break;
+                }
+            }
+            // every value has a piece in `next`, so nothing is left over
+            debug_assert!(rest.is_empty());
+        }
+        rules.retain(|r| r.in_start != r.out_start);
+        rules.sort_by_key(|r| r.in_start);
+        SeedMap { rules }
+    }
+
+    /// A chain of maps collapsed into one, e.g. the whole almanac into a
+    /// single seed to location map.
+    pub fn compose_all(maps: &[SeedMap]) -> SeedMap {
+        maps.iter()
+            .fold(SeedMap { rules: vec![] }, |composed, map| {
+                composed.compose(map)
+            })
+    }
+
+    /// Every value that translates to `value`, in increasing order.
+    pub fn translate_back(&self, value: u64) -> Vec<u64> {
+        self.pieces()
+            .iter()
+            .filter(|p| (p.out_start..p.out_end()).contains(&value))
+            .map(|p| p.in_start + (value - p.out_start))
+            .sorted()
+            .collect()
     }
 }
 
@@ -28,148 +178,426 @@ pub struct MapRule {
 }
 
 impl MapRule {
-    fn merge(self, other: MapRule) -> Vec<MapRule> {
-        let overlap = self.get_overlap(other);
+    fn identity(start: u64, end: u64) -> MapRule {
+        MapRule {
+            out_start: start,
+            in_start: start,
+            len: end - start,
+        }
+    }
+
+    fn in_end(&self) -> u64 {
+        self.in_start + self.len
+    }
 
-        todo!()
+    fn out_end(&self) -> u64 {
+        self.out_start + self.len
+    }
+
+    /// The part of this rule whose inputs are `start..end`, which must be
+    /// inside its input range.
+    fn restrict(self, start: u64, end: u64) -> MapRule {
+        MapRule {
+            out_start: self.out_start + (start - self.in_start),
+            in_start: start,
+            len: end - start,
+        }
+    }
+
+    /// This rule followed by `other`. The part of this rule whose output
+    /// `other` takes in becomes one rule going through both, returned first,
+    /// and the parts either side of it are left as they are.
+    fn merge(self, other: MapRule) -> (Option<MapRule>, Vec<MapRule>) {
+        let overlap = self.get_overlap(other);
+        if overlap.is_empty() {
+            return (None, vec![self]);
+        }
+        // back from this rule's output to its input
+        let in_start = self.in_start + (overlap.start - self.out_start);
+        let in_end = self.in_start + (overlap.end - self.out_start);
+        let both = MapRule {
+            out_start: other.out_start + (overlap.start - other.in_start),
+            in_start,
+            len: overlap.end - overlap.start,
+        };
+        let mut rest = vec![];
+        if self.in_start < in_start {
+            rest.push(self.restrict(self.in_start, in_start));
+        }
+        if in_end < self.in_end() {
+            rest.push(self.restrict(in_end, self.in_end()));
+        }
+        (Some(both), rest)
     }
 
     /// Get overlap of MapRules out_range and provided MapRules in_range.
     fn get_overlap(&self, other: MapRule) -> Range<u64> {
         let start = max(self.out_start, other.in_start);
-        let end = max(self.out_start, other.in_start);
-        start..end + 1
+        let end = min(self.out_end(), other.in_end());
+        start..end.max(start)
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct MapRange {
-    in_range: Range<u64>,
-    out_range: Range<u64>,
+pub fn solve_part_one(input: &str) -> u64 {
+    try_solve_part_one(input).unwrap_or_else(|e| panic!("{}", e))
 }
 
-pub fn solve_part_one(input: &str) -> u64 {
-    let split = input.split("\n\n").collect::<Vec<&str>>();
-    let seeds_info = parse_seed_info(split[0]);
-    let seed_to_soil_info = parse_map(split[1]);
-    let soil_to_fert_info = parse_map(split[2]);
-    let fert_to_water_info = parse_map(split[3]);
-    let water_to_light_info = parse_map(split[4]);
-    let light_to_temp_info = parse_map(split[5]);
-    let temp_to_humid_info = parse_map(split[6]);
-    let humid_to_locatin_info = parse_map(split[7]);
-
-    seeds_info
-        .par_iter()
-        .map(|s| seed_to_soil_info.translate(*s))
-        .map(|s| soil_to_fert_info.translate(s))
-        .map(|s| fert_to_water_info.translate(s))
-        .map(|s| water_to_light_info.translate(s))
-        .map(|s| light_to_temp_info.translate(s))
-        .map(|s| temp_to_humid_info.translate(s))
-        .map(|s| humid_to_locatin_info.translate(s))
+pub fn try_solve_part_one(input: &str) -> Result<u64, AlmanacError> {
+    let almanac = Almanac::parse(input)?;
+    almanac
+        .seeds
+        .iter()
+        .map(|&s| almanac.translate_all(s))
         .min()
-        .unwrap()
+        .ok_or(AlmanacError::NoSeeds)
 }
 
 pub fn solve_part_two(input: &str) -> u64 {
-    let split = input.split("\n\n").collect::<Vec<&str>>();
-    let seeds_info = parse_seed_info(split[0]);
-    let seed_to_soil_info = parse_map(split[1]);
-    let soil_to_fert_info = parse_map(split[2]);
-    let fert_to_water_info = parse_map(split[3]);
-    let water_to_light_info = parse_map(split[4]);
-    let light_to_temp_info = parse_map(split[5]);
-    let temp_to_humid_info = parse_map(split[6]);
-    let humid_to_locatin_info = parse_map(split[7]);
-
-    seeds_info
-        .into_iter()
-        .tuples()
-        .par_bridge()
-        .flat_map(|(start, len)| (start..(start + len)))
-        .map(|s| seed_to_soil_info.translate(s))
-        .map(|s| soil_to_fert_info.translate(s))
-        .map(|s| fert_to_water_info.translate(s))
-        .map(|s| water_to_light_info.translate(s))
-        .map(|s| light_to_temp_info.translate(s))
-        .map(|s| temp_to_humid_info.translate(s))
-        .map(|s| humid_to_locatin_info.translate(s))
+    try_solve_part_two(input).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Part two treats the seeds as `start len` pairs. Rather than translating
+/// every seed, whole ranges are pushed through the maps and the answer is the
+/// lowest start of the ranges that come out.
+pub fn try_solve_part_two(input: &str) -> Result<u64, AlmanacError> {
+    let almanac = Almanac::parse(input)?;
+    let mut seeds = almanac.seed_ranges()?;
+    // an empty range has no seed and so no location
+    seeds.retain(|r| !r.is_empty());
+
+    almanac
+        .maps()
+        .iter()
+        .fold(seeds, |ranges, map| map.translate_ranges(&ranges))
+        .iter()
+        .map(|r| r.start)
         .min()
-        .unwrap()
+        .ok_or(AlmanacError::NoSeeds)
 }
 
-fn parse_range(range_input: &str) -> MapRule {
-    let mut iter = range_input.split_ascii_whitespace();
-    MapRule {
-        out_start: iter.next().unwrap().parse::<u64>().unwrap(),
-        in_start: iter.next().unwrap().parse::<u64>().unwrap(),
-        len: iter.next().unwrap().parse::<u64>().unwrap(),
-    }
+fn parse_or_panic(input: &str) -> Almanac {
+    Almanac::parse(input).unwrap_or_else(|e| panic!("{}", e))
 }
 
-fn parse_seed_info(seed_input: &str) -> Vec<u64> {
-    let (_, nums) = seed_input.split_once(' ').unwrap();
-    nums.split_ascii_whitespace()
-        .flat_map(|n| n.parse::<u64>())
-        .collect()
+/// Parse the seed numbers and the maps, in chain order from the first
+/// category to the last.
+pub fn parse_almanac(input: &str) -> (Vec<u64>, Vec<SeedMap>) {
+    let almanac = parse_or_panic(input);
+    (almanac.seeds.clone(), almanac.into_maps())
 }
 
-fn parse_map(map_input: &str) -> SeedMap {
-    let rules: Vec<MapRule> = map_input
-        .lines()
-        .skip(1)
-        .par_bridge()
-        .map(parse_range)
-        .collect();
-    SeedMap { rules }
+/// Parse the `destination source length` lines of one map. `body` must be a
+/// slice of `source` so that errors point at the right line of the input.
+pub(crate) fn parse_rules(source: &str, body: &str) -> Result<SeedMap, ParseError> {
+    let lines: Vec<&str> = body.lines().collect();
+    let rules = lines
+        .iter()
+        .map(|line| {
+            let numbers: Vec<u64> = parse_within(source, line, integers)?;
+            match numbers[..] {
+                [out_start, in_start, len]
+                    if in_start.checked_add(len).is_some()
+                        && out_start.checked_add(len).is_some() =>
+                {
+                    Ok(MapRule {
+                        out_start,
+                        in_start,
+                        len,
+                    })
+                }
+                [_, _, _] => Err(ParseError::at(
+                    source,
+                    line,
+                    "rule runs past the largest value",
+                )),
+                _ => Err(ParseError::at(
+                    source,
+                    line,
+                    "expected destination, source and length",
+                )),
+            }
+        })
+        .collect::<Result<Vec<MapRule>, ParseError>>()?;
+    SeedMap::new(rules).map_err(|e| {
+        let first = ParseError::at(source, lines[e.first], "");
+        ParseError::at(
+            source,
+            lines[e.second],
+            format!("rule overlaps the rule on line {}", first.line),
+        )
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
-    const TEST_INPUT: &str = r#"seeds: 79 14 55 13
+    const TEST_INPUT: &str = include_str!("../../examples/day05/example.in");
 
-seed-to-soil map:
-50 98 2
-52 50 48
+    #[test]
+    fn test_solve_part_one() {
+        assert_eq!(solve_part_one(TEST_INPUT), 35);
+    }
 
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
+    #[test]
+    fn test_solve_part_two() {
+        assert_eq!(solve_part_two(TEST_INPUT), 46);
+    }
 
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
+    #[test]
+    fn test_seed_errors() {
+        let maps = "\n\nseed-to-soil map:\n50 98 2\n";
+        let input = |seeds: &str| format!("seeds:{}{}", seeds, maps);
+        assert!(matches!(
+            try_solve_part_one(&input("")),
+            Err(AlmanacError::Parse(_))
+        ));
+        assert_eq!(
+            try_solve_part_two(&input(" 98 0 5 0")),
+            Err(AlmanacError::NoSeeds)
+        );
+        assert_eq!(try_solve_part_two(&input(" 98 0 99 1")), Ok(51));
+        assert_eq!(
+            try_solve_part_two(&input(" 98 2 7")),
+            Err(AlmanacError::OddSeeds)
+        );
+        assert_eq!(
+            try_solve_part_two(&input(" 18446744073709551615 2")),
+            Err(AlmanacError::SeedRangeOverflow(u64::MAX))
+        );
+    }
 
-water-to-light map:
-88 18 7
-18 25 70
+    /// The original part two, translating every seed on its own.
+    fn solve_part_two_brute_force(input: &str) -> u64 {
+        let (seeds_info, maps) = parse_almanac(input);
+        seeds_info
+            .into_iter()
+            .tuples()
+            .flat_map(|(start, len)| start..(start + len))
+            .map(|s| maps.iter().fold(s, |s, map| map.translate(s)))
+            .min()
+            .unwrap()
+    }
 
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
+    /// A small pseudo-random almanac whose rules leave gaps and are listed
+    /// out of order.
+    fn generate_almanac(state: &mut u64, maps: usize) -> String {
+        let mut next = |bound: u64| {
+            *state ^= *state << 13;
+            *state ^= *state >> 7;
+            *state ^= *state << 17;
+            *state % bound
+        };
+        let seeds: Vec<String> = (0..4)
+            .map(|_| format!("{} {}", next(100), next(20)))
+            .collect();
+        let mut almanac = format!("seeds: {}\n", seeds.join(" "));
+        for m in 0..maps {
+            almanac += &format!("\n{}-to-{} map:\n", m, m + 1);
+            let mut rules = vec![];
+            let mut in_start = next(20);
+            for _ in 0..1 + next(4) {
+                let len = 1 + next(30);
+                rules.push(format!("{} {} {}\n", next(120), in_start, len));
+                in_start += len + next(10);
+            }
+            let shift = next(rules.len() as u64) as usize;
+            rules.rotate_left(shift);
+            almanac += &rules.concat();
+        }
+        almanac
+    }
 
-temperature-to-humidity map:
-0 69 1
-1 0 69
+    #[test]
+    fn test_translate_ranges() {
+        let (_, maps) = parse_almanac(TEST_INPUT);
+        // seed-to-soil: 98..100 -> 50..52, 50..98 -> 52..100
+        let mut ranges = maps[0].translate_ranges(&[45..55, 97..102, 3..3]);
+        ranges.sort_by_key(|r| r.start);
+        assert_eq!(ranges, [45..50, 50..52, 52..57, 99..100, 100..102]);
 
-humidity-to-location map:
-60 56 37
-56 93 4"#;
+        let mut state = 0x9e37_79b9_7f4a_7c15;
+        for _ in 0..200 {
+            let (_, maps) = parse_almanac(&generate_almanac(&mut state, 1));
+            let input = 0..150;
+            let mut expected: Vec<u64> = input.clone().map(|s| maps[0].translate(s)).collect();
+            let mut actual: Vec<u64> = maps[0]
+                .translate_ranges(&[input])
+                .into_iter()
+                .flatten()
+                .collect();
+            expected.sort_unstable();
+            actual.sort_unstable();
+            assert_eq!(actual, expected);
+        }
+    }
 
     #[test]
-    fn test_solve_part_one() {
-        assert_eq!(solve_part_one(TEST_INPUT), 35);
+    fn test_part_two_matches_brute_force() {
+        assert_eq!(solve_part_two_brute_force(TEST_INPUT), 46);
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..200 {
+            let almanac = generate_almanac(&mut state, 7);
+            assert_eq!(
+                solve_part_two(&almanac),
+                solve_part_two_brute_force(&almanac),
+                "{}",
+                almanac
+            );
+        }
+    }
+
+    fn rule(out_start: u64, in_start: u64, len: u64) -> MapRule {
+        MapRule {
+            out_start,
+            in_start,
+            len,
+        }
     }
 
     #[test]
-    fn test_solve_part_two() {
-        assert_eq!(solve_part_two(TEST_INPUT), 46);
+    fn test_merge() {
+        // 10..20 -> 100..110, then 105..120 -> 0..15
+        let (both, rest) = rule(100, 10, 10).merge(rule(0, 105, 15));
+        assert_eq!(both, Some(rule(0, 15, 5)));
+        assert_eq!(rest, [rule(100, 10, 5)]);
+
+        // the second rule takes the middle of the first one's output
+        let (both, rest) = rule(100, 10, 10).merge(rule(50, 102, 3));
+        assert_eq!(both, Some(rule(50, 12, 3)));
+        assert_eq!(rest, [rule(100, 10, 2), rule(105, 15, 5)]);
+
+        let (both, rest) = rule(100, 10, 10).merge(rule(0, 110, 5));
+        assert_eq!((both, rest), (None, vec![rule(100, 10, 10)]));
+
+        assert_eq!(rule(100, 10, 10).get_overlap(rule(0, 95, 10)), 100..105);
+        assert!(rule(100, 10, 10).get_overlap(rule(0, 110, 10)).is_empty());
+    }
+
+    #[test]
+    fn test_compose_example() {
+        let (seeds, maps) = parse_almanac(TEST_INPUT);
+        let composed = SeedMap::compose_all(&maps);
+        let locations: Vec<u64> = seeds.iter().map(|&s| composed.translate(s)).collect();
+        assert_eq!(locations, [82, 43, 86, 35]);
+        assert!(composed
+            .rules
+            .windows(2)
+            .all(|w| w[0].in_end() <= w[1].in_start));
+        // the lowest location of part two comes from seed 82
+        assert_eq!(composed.translate_back(46), [82]);
+        assert_eq!(composed.translate_back(82), [79]);
+    }
+
+    #[test]
+    fn test_compose_real_input() {
+        let input = include_str!("../input/day05.in");
+        let (seeds, maps) = parse_almanac(input);
+        let composed = SeedMap::compose_all(&maps);
+        let lowest = seeds.iter().map(|&s| composed.translate(s)).min();
+        assert_eq!(lowest, Some(solve_part_one(input)));
+        for &seed in &seeds {
+            assert!(composed
+                .translate_back(composed.translate(seed))
+                .contains(&seed));
+        }
+    }
+
+    /// Up to 4 rules below 250, with gaps between them and in any order.
+    fn arb_map() -> impl Strategy<Value = SeedMap> {
+        let spans = prop::collection::vec((0..20u64, 1..40u64, 0..200u64), 0..5);
+        spans
+            .prop_map(|spans| {
+                let mut in_start = 0;
+                let mut rules = vec![];
+                for (gap, len, out_start) in spans {
+                    rules.push(rule(out_start, in_start + gap, len));
+                    in_start += gap + len;
+                }
+                rules
+            })
+            .prop_shuffle()
+            .prop_map(|rules| SeedMap::new(rules).unwrap())
+    }
+
+    proptest! {
+        #[test]
+        fn test_compose_matches_sequential(
+            maps in prop::collection::vec(arb_map(), 0..5),
+            values in prop::collection::vec(0..300u64, 20),
+        ) {
+            let composed = SeedMap::compose_all(&maps);
+            for value in values {
+                let sequential = maps.iter().fold(value, |v, map| map.translate(v));
+                prop_assert_eq!(composed.translate(value), sequential);
+            }
+        }
+
+        #[test]
+        fn test_translate_back(maps in prop::collection::vec(arb_map(), 1..4), value in 0..300u64) {
+            // no rule reaches past 250, so anything at 300 or above maps to
+            // itself and cannot land below 300
+            let composed = SeedMap::compose_all(&maps);
+            let expected: Vec<u64> = (0..300).filter(|&s| composed.translate(s) == value).collect();
+            prop_assert_eq!(composed.translate_back(value), expected);
+        }
+    }
+
+    #[test]
+    fn test_sorted_rules() {
+        let map = SeedMap::new(vec![rule(0, 50, 10), rule(100, 10, 5), rule(7, 0, 0)]).unwrap();
+        assert_eq!(map.rules, [rule(100, 10, 5), rule(0, 50, 10)]);
+        let translated: Vec<u64> = [9, 10, 14, 15, 49, 50, 59, 60]
+            .map(|s| map.translate(s))
+            .into();
+        assert_eq!(translated, [9, 100, 104, 15, 49, 0, 9, 60]);
+
+        let err = SeedMap::new(vec![rule(0, 50, 10), rule(0, 0, 5), rule(0, 59, 1)]).unwrap_err();
+        assert_eq!(
+            err,
+            OverlapError {
+                first: 0,
+                second: 2
+            }
+        );
+        // touching rules do not overlap
+        assert!(SeedMap::new(vec![rule(0, 50, 10), rule(0, 60, 1)]).is_ok());
+    }
+
+    #[test]
+    fn test_rule_errors() {
+        let input = "seeds: 1\n\na-to-b map:\n0 50 10\n5 5 5\n0 55 2\n";
+        let err = Almanac::parse(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 6, column 1: rule overlaps the rule on line 4"
+        );
+        let input = "seeds: 1\n\na-to-b map:\n0 18446744073709551615 2\n";
+        let err = Almanac::parse(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 1: rule runs past the largest value"
+        );
+        let input = "seeds: 1\n\na-to-b map:\n18446744073709551615 0 2\n";
+        let err = Almanac::parse(input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 1: rule runs past the largest value"
+        );
+        let err = Almanac::parse("seeds: 1\n\na-to-b map:\n0 1\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 1: expected destination, source and length"
+        );
+    }
+
+    #[test]
+    fn test_parse_almanac() {
+        let (seeds, maps) = parse_almanac(TEST_INPUT);
+        assert_eq!(seeds, [79, 14, 55, 13]);
+        assert_eq!(maps.len(), 7);
+        assert_eq!(maps[0].translate(79), 81);
+        assert_eq!(maps[0].translate(10), 10);
     }
 }
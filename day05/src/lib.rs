@@ -1 +1,2 @@
+pub mod almanac;
 pub mod day05;
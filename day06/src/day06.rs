@@ -1,109 +1,229 @@
-use itertools::Itertools;
+use aoc_common::math::isqrt;
+use aoc_common::parse::{digit_groups, integers, labelled, line_ending, parse_all, ParseError};
+use nom::sequence::separated_pair;
+use num_bigint::BigUint;
+use std::fmt;
+use std::ops::RangeInclusive;
 
-pub fn solve_part_one(input: &str) -> u64 {
-    let lines: Vec<&str> = input.lines().collect();
+/// A race of `time` milliseconds and the `record` distance to beat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    pub time: u64,
+    pub record: u64,
+}
 
-    let (_, times_str) = lines[0].split_once(':').unwrap();
-    let times = times_str
-        .split_ascii_whitespace()
-        .flat_map(|n| n.parse::<u64>());
+impl Race {
+    /// How far the boat goes when the button is held for `hold`
+    /// milliseconds. Holding for the whole race or longer goes nowhere.
+    pub fn distance(&self, hold: u64) -> u128 {
+        hold as u128 * self.time.saturating_sub(hold) as u128
+    }
 
-    let (_, dists_str) = lines[1].split_once(':').unwrap();
-    let dists = dists_str
-        .split_ascii_whitespace()
-        .flat_map(|n| n.parse::<u64>());
+    /// Every hold time that beats the record, if any do.
+    pub fn winning_holds(&self) -> Option<RangeInclusive<u64>> {
+        winning_holds(self.time, self.record)
+    }
 
-    times
-        .zip(dists)
-        .map(|(t, d)| find_solution_binary_search(t, d))
-        .product()
-}
+    pub fn first_winning_hold(&self) -> Option<u64> {
+        self.winning_holds().map(|holds| *holds.start())
+    }
 
-pub fn solve_part_two(input: &str) -> u64 {
-    let lines: Vec<&str> = input.lines().collect();
+    pub fn last_winning_hold(&self) -> Option<u64> {
+        self.winning_holds().map(|holds| *holds.end())
+    }
 
-    let (_, times_str) = lines[0].split_once(':').unwrap();
-    let times = times_str.split_ascii_whitespace().collect_vec();
-    let time: u64 = times.concat().parse().unwrap();
+    pub fn ways_to_win(&self) -> u64 {
+        count_winning_holds(self.time, self.record)
+    }
 
-    let (_, dists_str) = lines[1].split_once(':').unwrap();
-    let dists = dists_str.split_ascii_whitespace().collect_vec();
-    let dist: u64 = dists.concat().parse().unwrap();
+    /// The hold time that goes furthest. For odd times the hold either side
+    /// of the middle goes just as far, this is the shorter one.
+    pub fn optimal_hold(&self) -> u64 {
+        self.time / 2
+    }
 
-    //find_solutions(time, dist)
-    find_solution_binary_search(time, dist)
+    pub fn optimal_distance(&self) -> u128 {
+        self.distance(self.optimal_hold())
+    }
+
+    /// How much further than the record the optimal hold goes, zero or
+    /// negative when the record cannot be beaten.
+    pub fn margin(&self) -> i128 {
+        self.optimal_distance() as i128 - self.record as i128
+    }
+}
+
+pub fn solve_part_one(input: &str) -> u64 {
+    parse_race_table(input)
+        .unwrap_or_else(|e| panic!("{}", e))
+        .iter()
+        .map(Race::ways_to_win)
+        .product()
 }
 
-// count be sped up if we were to binary search to find first
-// instead of iterating
-fn find_solutions(time: u64, dist: u64) -> u64 {
-    let mut first = 0;
-    for i in 0..time + 1 {
-        if is_solution(i, time, dist) {
-            first = i;
-            break;
+/// Why part two has no `u64` answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KernedRaceError {
+    Parse(ParseError),
+    /// There are more ways to win than fit in a `u64`, this is the exact
+    /// count.
+    TooManyWays(BigUint),
+}
+
+impl fmt::Display for KernedRaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KernedRaceError::Parse(e) => write!(f, "{}", e),
+            KernedRaceError::TooManyWays(count) => {
+                write!(f, "{} ways to win do not fit in a u64", count)
+            }
         }
     }
+}
 
-    get_total(time, first)
+impl std::error::Error for KernedRaceError {}
+
+impl From<ParseError> for KernedRaceError {
+    fn from(e: ParseError) -> Self {
+        KernedRaceError::Parse(e)
+    }
 }
 
-fn find_solution_binary_search(time: u64, dist: u64) -> u64 {
-    let first = binary_search_for_first(time, dist);
-    get_total(time, first)
+pub fn solve_part_two(input: &str) -> u64 {
+    try_solve_part_two(input).unwrap_or_else(|e| panic!("{}", e))
 }
 
-fn is_solution(guess: u64, time: u64, dist: u64) -> bool {
-    let time_left = time - guess;
-    let result_dist = guess * time_left;
-    result_dist > dist
+/// Part two, failing instead of wrapping when the answer is too big.
+pub fn try_solve_part_two(input: &str) -> Result<u64, KernedRaceError> {
+    let count = count_kerned_race(input)?;
+    u64::try_from(&count).map_err(|_| KernedRaceError::TooManyWays(count))
 }
 
-fn get_total(time: u64, first: u64) -> u64 {
-    let time_half = time.div_ceil(2);
-    let offset = if time % 2 == 0 { 1 } else { 0 };
-    ((time_half - first) * 2) + offset
+/// The exact number of ways to win part two's race, however many digits the
+/// joined numbers have. A race that fits in a `u64` goes through
+/// [`winning_holds`] and its `u128` arithmetic, longer ones use big integers.
+pub fn count_kerned_race(input: &str) -> Result<BigUint, ParseError> {
+    let (times, records) = parse_kerned_race(input)?;
+    let (time, record) = (times.concat(), records.concat());
+    if let (Ok(time), Ok(record)) = (time.parse::<u64>(), record.parse::<u64>()) {
+        return Ok(BigUint::from(count_winning_holds(time, record)));
+    }
+    // both are only digits, so they always parse as big integers
+    let time: BigUint = time.parse().expect("time is digits");
+    let record: BigUint = record.parse().expect("record is digits");
+    Ok(winning_holds_big(&time, &record)
+        .map_or_else(BigUint::default, |(first, last)| last - first + 1u32))
 }
 
-fn binary_search_for_first(time: u64, dist: u64) -> u64 {
-    // println!("TIME: {}, DIST: {}", time, dist);
-    let mut low: u64 = 1;
-    let mut high: u64 = time;
-    let mut mid: u64 = high.div_ceil(2);
+/// Parse the `Time:` and `Distance:` rows as separate numbers.
+pub fn parse_races(input: &str) -> Result<(Vec<u64>, Vec<u64>), ParseError> {
+    parse_all(
+        input,
+        separated_pair(
+            labelled("Time", integers::<u64>),
+            line_ending,
+            labelled("Distance", integers::<u64>),
+        ),
+    )
+}
 
-    loop {
-        let last_works = is_solution(mid - 1, time, dist);
-        let here_works = is_solution(mid, time, dist);
+/// Parse the races, one per column of the `Time:` and `Distance:` rows. Both
+/// rows must have the same number of columns.
+pub fn parse_race_table(input: &str) -> Result<Vec<Race>, ParseError> {
+    let (times, records) = parse_races(input)?;
+    if times.len() != records.len() {
+        // point at the first column without a partner
+        let (time_groups, record_groups) = parse_kerned_race(input)?;
+        let (extra, message) = if times.len() > records.len() {
+            (time_groups[records.len()], "time without a distance")
+        } else {
+            (record_groups[times.len()], "distance without a time")
+        };
+        return Err(ParseError::at(input, extra, message));
+    }
+    Ok(times
+        .into_iter()
+        .zip(records)
+        .map(|(time, record)| Race { time, record })
+        .collect())
+}
 
-        if !last_works && here_works {
-            return mid;
-        }
+/// Parse the `Time:` and `Distance:` rows as the raw digit groups, which part
+/// two joins together since the spaces are just bad kerning.
+pub fn parse_kerned_race(input: &str) -> Result<(Vec<&str>, Vec<&str>), ParseError> {
+    parse_all(
+        input,
+        separated_pair(
+            labelled("Time", digit_groups),
+            line_ending,
+            labelled("Distance", digit_groups),
+        ),
+    )
+}
 
-        // perfect
-        if !last_works && here_works {
-            return mid;
-        }
+/// The hold times that beat `record` in a race of `time` milliseconds, or
+/// `None` if no hold does. Holding for `h` travels `h * (time - h)`, so the
+/// winning holds lie strictly between the roots of
+/// `h^2 - time * h + record = 0`, which are `(time ± sqrt(time^2 - 4 * record)) / 2`.
+/// The arithmetic is done in `u128`, which fits `time^2` for any `u64` time.
+pub fn winning_holds(time: u64, record: u64) -> Option<RangeInclusive<u64>> {
+    let (t, d) = (time as u128, record as u128);
+    let wins = |h: u128| h * (t - h) > d;
+    let discriminant = (t * t).checked_sub(4 * d)?;
 
-        // we are too high
-        if here_works {
-            high = mid;
-            mid = (high - low).div_ceil(2);
-        }
+    // the integer square root rounds down, which puts this at most one
+    // below the first win, or right on the lower root when the root is an
+    // integer and only ties the record
+    let mut first = (t - isqrt(discriminant)) / 2;
+    while first <= t / 2 && !wins(first) {
+        first += 1;
+    }
+    if first > t / 2 {
+        return None;
+    }
+    while first > 0 && wins(first - 1) {
+        first -= 1;
+    }
+    // distances are symmetric around half the race
+    let first = first as u64;
+    Some(first..=time - first)
+}
 
-        // we are too low
-        if !here_works {
-            low = mid;
-            mid = low + ((high - low) / 2);
-        }
+/// [`winning_holds`] for races of any size, as the first and last winning
+/// hold.
+fn winning_holds_big(time: &BigUint, record: &BigUint) -> Option<(BigUint, BigUint)> {
+    let wins = |h: &BigUint| h * (time - h) > *record;
+    let (square, four_record) = (time * time, record * 4u32);
+    if square < four_record {
+        return None;
+    }
+    let discriminant = square - four_record;
+
+    let half = time / 2u32;
+    let mut first = (time - discriminant.sqrt()) / 2u32;
+    while first <= half && !wins(&first) {
+        first += 1u32;
+    }
+    if first > half {
+        return None;
+    }
+    while first > BigUint::default() && wins(&(&first - 1u32)) {
+        first -= 1u32;
     }
+    let last = time - &first;
+    Some((first, last))
+}
+
+/// How many hold times beat `record` in a race of `time` milliseconds.
+pub fn count_winning_holds(time: u64, record: u64) -> u64 {
+    winning_holds(time, record).map_or(0, |holds| holds.end() - holds.start() + 1)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = r#"Time:      7  15   30
-Distance:  9  40  200"#;
+    const TEST_INPUT: &str = include_str!("../../examples/day06/example.in");
 
     #[test]
     fn test_solve_part_one() {
@@ -111,14 +231,215 @@ Distance:  9  40  200"#;
     }
 
     #[test]
-    fn test_binary_search_one_example() {
-        assert_eq!(binary_search_for_first(7, 9), 2);
-        assert_eq!(binary_search_for_first(15, 40), 4);
-        assert_eq!(binary_search_for_first(30, 200), 11);
+    fn test_winning_holds_example() {
+        assert_eq!(winning_holds(7, 9), Some(2..=5));
+        assert_eq!(winning_holds(15, 40), Some(4..=11));
+        // holding 10 or 20 only ties the record of 200
+        assert_eq!(winning_holds(30, 200), Some(11..=19));
+        assert_eq!(winning_holds(4, 4), None);
+        assert_eq!(winning_holds(4, 3), Some(2..=2));
+        assert_eq!(winning_holds(0, 0), None);
+        assert_eq!(winning_holds(3, 100), None);
+    }
+
+    #[test]
+    fn test_winning_holds_brute_force() {
+        for time in 0..60u64 {
+            for record in 0..time * time / 4 + 3 {
+                let wins: Vec<u64> = (0..=time).filter(|h| h * (time - h) > record).collect();
+                let expected = wins.first().map(|&first| first..=*wins.last().unwrap());
+                assert_eq!(winning_holds(time, record), expected, "{} {}", time, record);
+            }
+        }
+    }
+
+    #[test]
+    fn test_winning_holds_large() {
+        let distance = |h: u64, time: u64| h as u128 * (time - h) as u128;
+        for (time, record) in [
+            (u64::MAX, u64::MAX),
+            (u64::MAX - 1, 1 << 62),
+            (1 << 40, u64::MAX),
+            (4_000_000_000, 3_999_999_999_999_999_999),
+        ] {
+            let holds = winning_holds(time, record).unwrap();
+            let (first, last) = (*holds.start(), *holds.end());
+            assert!(distance(first, time) > record as u128);
+            assert!(distance(first - 1, time) <= record as u128);
+            assert!(distance(last, time) > record as u128);
+            assert!(distance(last + 1, time) <= record as u128);
+        }
+        // only the middle hold travels 2^64, its neighbours tie the record
+        // at (2^32 - 1) * (2^32 + 1)
+        assert_eq!(winning_holds(1 << 33, u64::MAX), Some(1 << 32..=1 << 32));
+    }
+
+    #[test]
+    fn test_race() {
+        let race = Race { time: 7, record: 9 };
+        assert_eq!(race.first_winning_hold(), Some(2));
+        assert_eq!(race.last_winning_hold(), Some(5));
+        assert_eq!(race.ways_to_win(), 4);
+        assert_eq!((race.optimal_hold(), race.optimal_distance()), (3, 12));
+        assert_eq!(race.distance(4), 12);
+        assert_eq!(
+            (race.distance(7), race.distance(8), race.distance(u64::MAX)),
+            (0, 0, 0)
+        );
+        assert_eq!(race.margin(), 3);
+
+        let race = Race {
+            time: 30,
+            record: 200,
+        };
+        assert_eq!((race.optimal_hold(), race.optimal_distance()), (15, 225));
+        assert_eq!(race.margin(), 25);
+
+        // the best this race can do is tie
+        let tie = Race { time: 4, record: 4 };
+        assert_eq!(tie.first_winning_hold(), None);
+        assert_eq!((tie.ways_to_win(), tie.margin()), (0, 0));
+        assert_eq!(Race { time: 4, record: 9 }.margin(), -5);
+
+        let race = Race {
+            time: u64::MAX,
+            record: 0,
+        };
+        assert_eq!(
+            race.optimal_distance(),
+            (u64::MAX / 2) as u128 * (u64::MAX / 2 + 1) as u128
+        );
+    }
+
+    #[test]
+    fn test_parse_race_table() {
+        assert_eq!(
+            parse_race_table(TEST_INPUT).unwrap(),
+            [
+                Race { time: 7, record: 9 },
+                Race {
+                    time: 15,
+                    record: 40
+                },
+                Race {
+                    time: 30,
+                    record: 200
+                },
+            ]
+        );
+
+        let err = parse_race_table("Time: 7 15 30\nDistance: 9 40").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 12: time without a distance"
+        );
+        let err = parse_race_table("Time: 7\nDistance: 9 40").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 13: distance without a time"
+        );
+    }
+
+    #[test]
+    fn test_parse_races_error() {
+        let err = parse_races("Time: 7 15\nDistance 9 40").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 10));
+        assert_eq!(err.message, "expected ':' in Distance");
     }
 
     #[test]
     fn test_solve_part_two() {
         assert_eq!(solve_part_two(TEST_INPUT), 71503);
     }
+
+    fn big(n: u64) -> BigUint {
+        BigUint::from(n)
+    }
+
+    #[test]
+    fn test_winning_holds_big_matches_u64() {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..1000 {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            let time = state >> (state % 60);
+            let record = (time as u128 * time as u128 / 4) as u64 >> (state % 5);
+            let expected = winning_holds(time, record).map(|h| (big(*h.start()), big(*h.end())));
+            assert_eq!(winning_holds_big(&big(time), &big(record)), expected);
+        }
+    }
+
+    /// A kerned race input with `time` and `record` split into groups of up
+    /// to 4 digits.
+    fn kerned(time: &str, record: &str) -> String {
+        let groups = |n: &str| {
+            let digits: Vec<char> = n.chars().collect();
+            digits
+                .chunks(4)
+                .map(|c| c.iter().collect::<String>())
+                .collect::<Vec<_>>()
+                .join("  ")
+        };
+        format!(
+            "Time:      {}\nDistance:  {}\n",
+            groups(time),
+            groups(record)
+        )
+    }
+
+    #[test]
+    fn test_long_kerned_race() {
+        // generated times of 21 to 40 digits
+        let mut time = String::from("1");
+        for i in 0..40u32 {
+            time.push(char::from_digit((i * 7 + 3) % 10, 10).unwrap());
+            if time.len() <= 20 {
+                continue;
+            }
+            let t: BigUint = time.parse().unwrap();
+
+            // with a record of 0 every hold but 0 and the full time wins
+            let input = kerned(&time, "0");
+            assert_eq!(count_kerned_race(&input), Ok(&t - 1u32));
+            assert_eq!(
+                try_solve_part_two(&input),
+                Err(KernedRaceError::TooManyWays(&t - 1u32))
+            );
+
+            // the best distance only ties, one less and only the middle wins
+            if &t % 2u32 == BigUint::default() {
+                let best = (&t / 2u32) * (&t / 2u32);
+                let input = kerned(&time, &best.to_string());
+                assert_eq!(try_solve_part_two(&input), Ok(0));
+                let input = kerned(&time, &(&best - 1u32).to_string());
+                assert_eq!(try_solve_part_two(&input), Ok(1));
+            }
+
+            // a record that leaves a small window around the middle
+            let half = &t / 2u32;
+            let record = &half * (&t - &half) - 1000u32;
+            let (first, last) = winning_holds_big(&t, &record).unwrap();
+            let distance = |h: &BigUint| h * (&t - h);
+            assert!(distance(&first) > record && distance(&(&first - 1u32)) <= record);
+            assert!(distance(&last) > record && distance(&(&last + 1u32)) <= record);
+            let input = kerned(&time, &record.to_string());
+            assert_eq!(
+                try_solve_part_two(&input),
+                Ok(u64::try_from(last - first + 1u32).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn test_solve_part_two_errors() {
+        let err = try_solve_part_two("Time: 7 x\nDistance: 9").unwrap_err();
+        assert!(matches!(err, KernedRaceError::Parse(_)));
+        let input = kerned("123456789012345678901234", "5");
+        let err = try_solve_part_two(&input).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "123456789012345678901233 ways to win do not fit in a u64"
+        );
+    }
 }
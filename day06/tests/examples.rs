@@ -0,0 +1,5 @@
+//! Runs the solvers against every example in `examples/day06`.
+
+use aoc_2023::day06::*;
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));
@@ -0,0 +1,4 @@
+fn main() {
+    // example tests for tests/examples.rs, generated from examples/day07
+    aoc_common::examples::generate_tests("day07");
+}
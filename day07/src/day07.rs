@@ -172,11 +172,7 @@ fn parse_line(line: &str, joker_rules: bool) -> Hand {
 mod tests {
     use super::*;
 
-    const TEST_INPUT: &str = r#"32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483"#;
+    const TEST_INPUT: &str = include_str!("../../examples/day07/example.in");
 
     const HAND_A: Hand = Hand {
         hand_type: HandType::OnePair,
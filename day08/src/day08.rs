@@ -1,19 +1,24 @@
+use aoc_common::math::lcm;
+use aoc_common::parse::{key_tuple, parse_within, sections, word, PResult, ParseError};
 use core::panic;
+use nom::bytes::complete::is_a;
+use nom::combinator::map;
+use nom::error::context;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use std::collections::HashMap;
 
 #[derive(Debug)]
 pub struct Location<'a> {
-    name: &'a str,
-    left: &'a str,
-    right: &'a str,
+    pub name: &'a str,
+    pub left: &'a str,
+    pub right: &'a str,
 }
 
 pub fn solve_part_one(input: &str) -> u64 {
-    let (directions, location_section) = input.split_once("\n\n").unwrap();
+    let (directions, nodes) = parse_network(input).unwrap_or_else(|e| panic!("{}", e));
 
     let mut locations = HashMap::new();
-    location_section.lines().map(parse_location).for_each(|l| {
+    nodes.into_iter().for_each(|l| {
         locations.insert(l.name, l);
     });
 
@@ -60,27 +65,12 @@ pub fn find_cycle_length<'a>(
     turns
 }
 
-pub fn gcd(mut n: u64, mut m: u64) -> u64 {
-    assert!(n != 0 && m != 0);
-    while m != 0 {
-        if m < n {
-            std::mem::swap(&mut m, &mut n);
-        }
-        m %= n;
-    }
-    n
-}
-
-pub fn lcm(m: u64, n: u64) -> u64 {
-    (m * n) / gcd(m, n)
-}
-
 pub fn solve_part_two(input: &str) -> u64 {
-    let (directions, location_section) = input.split_once("\n\n").unwrap();
+    let (directions, nodes) = parse_network(input).unwrap_or_else(|e| panic!("{}", e));
 
     let mut locations = HashMap::new();
     let mut current_locations = Vec::with_capacity(100);
-    location_section.lines().map(parse_location).for_each(|l| {
+    nodes.into_iter().for_each(|l| {
         let name = l.name;
         locations.insert(name, l);
         if name.ends_with('A') {
@@ -88,55 +78,50 @@ pub fn solve_part_two(input: &str) -> u64 {
         }
     });
 
-    let lcm: u64 = current_locations
+    current_locations
         .par_iter()
         .map(|loc| find_cycle_length(loc, directions, &locations))
-        .reduce(|| 1, lcm);
-
-    lcm
+        .map(Some)
+        .reduce(|| Some(1), |a, b| lcm(a?, b?))
+        .expect("lcm of cycle lengths to fit in a u64")
 }
 
-fn parse_location(line: &str) -> Location {
-    let (name, turn_section) = line.split_once(" = ").unwrap();
-    let (left_part, right_part) = turn_section.split_once(", ").unwrap();
+/// Parse the turn directions and the list of locations, reporting the line
+/// and column of anything that is not in the `AAA = (BBB, CCC)` format.
+pub fn parse_network(input: &str) -> Result<(&str, Vec<Location<'_>>), ParseError> {
+    let mut sections = sections(input);
+    let end = &input[input.len()..];
+
+    let directions = parse_within(
+        input,
+        sections.next().unwrap_or(end),
+        context("L or R directions", is_a("LR")),
+    )?;
+    let locations = sections
+        .next()
+        .unwrap_or(end)
+        .lines()
+        .map(|line| parse_within(input, line, parse_location))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((directions, locations))
+}
 
-    Location {
-        name,
-        left: &left_part[1..],
-        right: &right_part[..3],
-    }
+fn parse_location(line: &str) -> PResult<'_, Location<'_>> {
+    map(key_tuple(word, word, word), |(name, (left, right))| {
+        Location { name, left, right }
+    })(line)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    const TEST_EXAMPLE_ONE: &str = r#"RL
-
-AAA = (BBB, CCC)
-BBB = (DDD, EEE)
-CCC = (ZZZ, GGG)
-DDD = (DDD, DDD)
-EEE = (EEE, EEE)
-GGG = (GGG, GGG)
-ZZZ = (ZZZ, ZZZ)"#;
-
-    const TEST_EXAMPLE_TWO: &str = r#"LLR
-
-AAA = (BBB, BBB)
-BBB = (AAA, ZZZ)
-ZZZ = (ZZZ, ZZZ)"#;
+    const TEST_EXAMPLE_ONE: &str = include_str!("../../examples/day08/example_one.in");
 
-    const TEST_EXAMPLE_THREE: &str = r#"LR
+    const TEST_EXAMPLE_TWO: &str = include_str!("../../examples/day08/example_two.in");
 
-11A = (11B, XXX)
-11B = (XXX, 11Z)
-11Z = (11B, XXX)
-22A = (22B, XXX)
-22B = (22C, 22C)
-22C = (22Z, 22Z)
-22Z = (22B, 22B)
-XXX = (XXX, XXX)"#;
+    const TEST_EXAMPLE_THREE: &str = include_str!("../../examples/day08/example_three.in");
 
     #[test]
     fn test_solve_part_one_example_one() {
@@ -148,6 +133,15 @@ XXX = (XXX, XXX)"#;
         assert_eq!(solve_part_one(TEST_EXAMPLE_TWO), 6);
     }
 
+    #[test]
+    fn test_parse_network_error() {
+        let err = parse_network("RL\n\nAAA = (BBB, CCC)\nBBB = DDD, EEE)").unwrap_err();
+        assert_eq!((err.line, err.column), (4, 7));
+
+        let err = parse_network("RXL\n\nAAA = (BBB, CCC)").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 2));
+    }
+
     #[test]
     fn test_solve_part_two() {
         assert_eq!(solve_part_two(TEST_EXAMPLE_THREE), 6);
@@ -90,11 +90,9 @@ fn predict_next(history: &str) -> i64 {
 mod tests {
     use super::*;
 
-    const TEST_EXAMPLE: &str = r#"0 3 6 9 12 15
-    1 3 6 10 15 21
-    10 13 16 21 30 45"#;
+    const TEST_EXAMPLE: &str = include_str!("../../examples/day09/example.in");
 
-    const TEST_PART_TWO_EXAMPLE: &str = r#"10 13 16 21 30 45"#;
+    const TEST_PART_TWO_EXAMPLE: &str = include_str!("../../examples/day09/example_part_two.in");
 
     #[test]
     fn test_solve_part_one() {
@@ -100,8 +100,8 @@ impl TileContents {
 
 #[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
 pub struct Point {
-    x: usize,
-    y: usize,
+    pub x: usize,
+    pub y: usize,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -327,6 +327,20 @@ pub fn solve_part_two(input: &str) -> u32 {
         .sum()
 }
 
+/// Every point on the loop through the start tile, in the order they are
+/// walked starting from the start tile.
+pub fn loop_path(input: &str) -> Vec<Point> {
+    let matrix = Matrix::from(input);
+    let start_tile = matrix.find_start();
+    find_loop_path(
+        &matrix,
+        &start_tile,
+        &start_tile,
+        &mut Vec::with_capacity(15000),
+    )
+    .expect("start tile to be part of a loop")
+}
+
 pub fn find_loop_length(matrix: &Matrix, current: &Tile, last: &Tile, len: u32) -> Option<u32> {
     let mut iter = matrix.neighbor_iter(&current.point).peekable();
     iter.peek()?;
@@ -374,21 +388,9 @@ mod tests {
 
     use super::*;
 
-    const TEST_EXAMPLE: &str = r#"-L|F7
-7S-7|
-L|7||
--L-J|
-L|-JF"#;
-
-    const TEST_EXAMPLE_TWO: &str = r#"...........
-.S-------7.
-.|F-----7|.
-.||.....||.
-.||.....||.
-.|L-7.F-J|.
-.|..|.|..|.
-.L--J.L--J.
-..........."#;
+    const TEST_EXAMPLE: &str = include_str!("../../examples/day10/example_one.in");
+
+    const TEST_EXAMPLE_TWO: &str = include_str!("../../examples/day10/example_two.in");
 
     #[test]
     fn test_solve_part_one_example_two() {
@@ -435,4 +437,11 @@ L|-JF"#;
         .unwrap();
         assert_eq!(path.len(), 46);
     }
+
+    #[test]
+    fn test_loop_path() {
+        let path = loop_path(TEST_EXAMPLE);
+        assert_eq!(path.len(), 8);
+        assert_eq!(path[0], Point { x: 1, y: 1 });
+    }
 }
@@ -113,16 +113,7 @@ pub fn solve_part_two(input: &str, expansion_val: usize) -> usize {
 mod tests {
     use super::*;
 
-    const TEST_EXAMPLE: &str = r#"...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#....."#;
+    const TEST_EXAMPLE: &str = include_str!("../../examples/day11/example.in");
     #[test]
     fn test_solve_part_one() {
         assert_eq!(solve_part_one(TEST_EXAMPLE), 374);
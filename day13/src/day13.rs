@@ -138,7 +138,7 @@ pub fn solve_part_two(_input: &str) -> u64 {
 mod tests {
     use super::*;
 
-    const TEST_EXAMPLE: &str = r#""#;
+    const TEST_EXAMPLE: &str = include_str!("../../examples/day13/example.in");
 
     const TEST_EXAMPLE_HORIZONTAL: &str = r#"#...##..#
 #....#..#
@@ -187,11 +187,12 @@ mod tests {
 
     #[test]
     fn test_solve_part_one() {
-        assert_eq!(solve_part_one(TEST_EXAMPLE), 21);
+        assert_eq!(solve_part_one(TEST_EXAMPLE), 405);
     }
 
     #[test]
+    #[ignore = "part two is not implemented yet"]
     fn test_solve_part_two() {
-        assert_eq!(solve_part_two(TEST_EXAMPLE), 525152);
+        assert_eq!(solve_part_two(TEST_EXAMPLE), 400);
     }
 }
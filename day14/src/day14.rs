@@ -79,16 +79,7 @@ pub fn solve_part_two(input: &str) -> usize {
 mod tests {
     use super::*;
 
-    const TEST_EXAMPLE: &str = r#"O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#...."#;
+    const TEST_EXAMPLE: &str = include_str!("../../examples/day14/example.in");
 
     #[test]
     fn test_solve_part_one() {
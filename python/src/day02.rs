@@ -0,0 +1,28 @@
+use ::day02::day02::{self, Strategy};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+fn strategy(name: Option<&str>) -> PyResult<Strategy> {
+    name.map_or(Ok(Strategy::default()), |name| {
+        name.parse().map_err(PyValueError::new_err)
+    })
+}
+
+#[pyfunction]
+#[pyo3(signature = (input, strategy = None))]
+fn solve_part_one(input: &str, strategy: Option<&str>) -> PyResult<u32> {
+    let strategy = self::strategy(strategy)?;
+    crate::catch(|| day02::solve_part_one_with(input, strategy))
+}
+
+#[pyfunction]
+#[pyo3(signature = (input, strategy = None))]
+fn solve_part_two(input: &str, strategy: Option<&str>) -> PyResult<u64> {
+    let strategy = self::strategy(strategy)?;
+    crate::catch(|| day02::solve_part_two_with(input, strategy))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve_part_one, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_two, m)?)
+}
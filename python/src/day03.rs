@@ -0,0 +1,73 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// The numbers and symbols of a schematic and which of them touch.
+#[pyclass(module = "aoc_2023.day03")]
+pub struct Schematic {
+    inner: ::day03::graph::Schematic,
+}
+
+#[pymethods]
+impl Schematic {
+    /// Every number as `(value, row, start, end)`, `end` being one past its
+    /// last column.
+    #[getter]
+    fn numbers(&self) -> Vec<(i64, usize, usize, usize)> {
+        let numbers = self.inner.numbers.iter();
+        numbers.map(|n| (n.value, n.row, n.start, n.end)).collect()
+    }
+
+    /// Every symbol as `(kind, row, col)`.
+    #[getter]
+    fn symbols(&self) -> Vec<(char, usize, usize)> {
+        let symbols = self.inner.symbols.iter();
+        symbols.map(|s| (s.kind, s.row, s.col)).collect()
+    }
+
+    /// Indices of the symbols touching number `n`.
+    fn symbols_of(&self, n: usize) -> Vec<usize> {
+        self.inner.symbols_of(n).to_vec()
+    }
+
+    /// Indices of the numbers touching symbol `s`.
+    fn numbers_of(&self, s: usize) -> Vec<usize> {
+        self.inner.numbers_of(s).to_vec()
+    }
+
+    /// Values of the numbers touching at least one symbol.
+    fn part_numbers(&self) -> Vec<i64> {
+        self.inner.part_numbers().map(|n| n.value).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Schematic(numbers={}, symbols={})",
+            self.inner.numbers.len(),
+            self.inner.symbols.len()
+        )
+    }
+}
+
+#[pyfunction]
+fn parse_schematic(input: &str) -> PyResult<Schematic> {
+    let inner = ::day03::graph::Schematic::parse(input)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(Schematic { inner })
+}
+
+#[pyfunction]
+fn solve_part_one(input: &str) -> PyResult<i64> {
+    crate::catch(|| ::day03::day03::solve_part_one(input))
+}
+
+#[pyfunction]
+fn solve_part_two(input: &str) -> PyResult<i64> {
+    crate::catch(|| ::day03::day03::solve_part_two(input))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Schematic>()?;
+    m.add_function(wrap_pyfunction!(parse_schematic, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_one, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_two, m)?)
+}
@@ -0,0 +1,59 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// A table of scratchcards, as the number of winners on every card.
+#[pyclass(module = "aoc_2023.day04")]
+pub struct Scratchcards {
+    inner: ::day04::day04::Scratchcards,
+}
+
+#[pymethods]
+impl Scratchcards {
+    #[getter]
+    fn winners(&self) -> Vec<u32> {
+        self.inner.winners().to_vec()
+    }
+
+    /// The total score, `None` if it does not fit in a `u64`.
+    fn score(&self) -> Option<u64> {
+        self.inner.score()
+    }
+
+    fn total_cards(&self) -> PyResult<u64> {
+        self.inner
+            .total_cards()
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn __len__(&self) -> usize {
+        self.inner.winners().len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Scratchcards(len={})", self.inner.winners().len())
+    }
+}
+
+#[pyfunction]
+fn parse_scratchcards(input: &str) -> PyResult<Scratchcards> {
+    let inner = ::day04::day04::Scratchcards::parse(input)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    Ok(Scratchcards { inner })
+}
+
+#[pyfunction]
+fn solve_part_one(input: &str) -> PyResult<u64> {
+    crate::catch(|| ::day04::day04::solve_part_one(input))
+}
+
+#[pyfunction]
+fn solve_part_two(input: &str) -> PyResult<u64> {
+    ::day04::day04::try_solve_part_two(input).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Scratchcards>()?;
+    m.add_function(wrap_pyfunction!(parse_scratchcards, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_one, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_two, m)?)
+}
@@ -0,0 +1,50 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// One `x-to-y map:` section of the almanac.
+#[pyclass(module = "aoc_2023.day05")]
+pub struct SeedMap {
+    inner: ::day05::day05::SeedMap,
+}
+
+#[pymethods]
+impl SeedMap {
+    fn translate(&self, value: u64) -> u64 {
+        self.inner.translate(value)
+    }
+
+    fn __call__(&self, value: u64) -> u64 {
+        self.inner.translate(value)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.inner)
+    }
+}
+
+/// The seed numbers and the maps of the almanac, from seed to location.
+#[pyfunction]
+fn parse_almanac(input: &str) -> PyResult<(Vec<u64>, Vec<SeedMap>)> {
+    let almanac = ::day05::almanac::Almanac::parse(input)
+        .map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let seeds = almanac.seeds.clone();
+    let maps = almanac.into_maps().into_iter();
+    Ok((seeds, maps.map(|inner| SeedMap { inner }).collect()))
+}
+
+#[pyfunction]
+fn solve_part_one(input: &str) -> PyResult<u64> {
+    ::day05::day05::try_solve_part_one(input).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+fn solve_part_two(input: &str) -> PyResult<u64> {
+    ::day05::day05::try_solve_part_two(input).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<SeedMap>()?;
+    m.add_function(wrap_pyfunction!(parse_almanac, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_one, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_two, m)?)
+}
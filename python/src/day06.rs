@@ -0,0 +1,31 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// The `Time:` and `Distance:` rows as lists of numbers.
+#[pyfunction]
+fn parse_races(input: &str) -> PyResult<(Vec<u64>, Vec<u64>)> {
+    ::day06::day06::parse_races(input).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+/// The `Time:` and `Distance:` rows as the digit groups part two joins.
+#[pyfunction]
+fn parse_kerned_race(input: &str) -> PyResult<(Vec<&str>, Vec<&str>)> {
+    ::day06::day06::parse_kerned_race(input).map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+#[pyfunction]
+fn solve_part_one(input: &str) -> PyResult<u64> {
+    crate::catch(|| ::day06::day06::solve_part_one(input))
+}
+
+#[pyfunction]
+fn solve_part_two(input: &str) -> PyResult<u64> {
+    crate::catch(|| ::day06::day06::solve_part_two(input))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse_races, m)?)?;
+    m.add_function(wrap_pyfunction!(parse_kerned_race, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_one, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_two, m)?)
+}
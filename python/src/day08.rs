@@ -0,0 +1,31 @@
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// The directions and a list of `(name, left, right)` locations.
+#[pyfunction]
+#[allow(clippy::type_complexity)]
+fn parse_network(input: &str) -> PyResult<(&str, Vec<(&str, &str, &str)>)> {
+    let (directions, locations) =
+        ::day08::day08::parse_network(input).map_err(|e| PyValueError::new_err(e.to_string()))?;
+    let locations = locations
+        .into_iter()
+        .map(|l| (l.name, l.left, l.right))
+        .collect();
+    Ok((directions, locations))
+}
+
+#[pyfunction]
+fn solve_part_one(input: &str) -> PyResult<u64> {
+    crate::catch(|| ::day08::day08::solve_part_one(input))
+}
+
+#[pyfunction]
+fn solve_part_two(input: &str) -> PyResult<u64> {
+    crate::catch(|| ::day08::day08::solve_part_two(input))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(parse_network, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_one, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_two, m)?)
+}
@@ -0,0 +1,59 @@
+use pyo3::prelude::*;
+
+/// The pipe loop through the start tile as `(x, y)` points in walking order.
+#[pyclass(module = "aoc_2023.day10", sequence)]
+pub struct LoopPath {
+    points: Vec<(usize, usize)>,
+}
+
+#[pymethods]
+impl LoopPath {
+    #[getter]
+    fn points(&self) -> Vec<(usize, usize)> {
+        self.points.clone()
+    }
+
+    /// Steps from the start tile to the point farthest along the loop, which
+    /// is the part one answer.
+    #[getter]
+    fn farthest(&self) -> usize {
+        self.points.len().div_ceil(2)
+    }
+
+    fn __len__(&self) -> usize {
+        self.points.len()
+    }
+
+    fn __contains__(&self, point: (usize, usize)) -> bool {
+        self.points.contains(&point)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("LoopPath(len={})", self.points.len())
+    }
+}
+
+#[pyfunction]
+fn loop_path(input: &str) -> PyResult<LoopPath> {
+    let points = crate::catch(|| ::day10::day10::loop_path(input))?;
+    Ok(LoopPath {
+        points: points.into_iter().map(|p| (p.x, p.y)).collect(),
+    })
+}
+
+#[pyfunction]
+fn solve_part_one(input: &str) -> PyResult<u32> {
+    crate::catch(|| ::day10::day10::solve_part_one(input))
+}
+
+#[pyfunction]
+fn solve_part_two(input: &str) -> PyResult<u32> {
+    crate::catch(|| ::day10::day10::solve_part_two(input))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<LoopPath>()?;
+    m.add_function(wrap_pyfunction!(loop_path, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_one, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_two, m)?)
+}
@@ -0,0 +1,18 @@
+use pyo3::prelude::*;
+
+#[pyfunction]
+fn solve_part_one(input: &str) -> PyResult<usize> {
+    crate::catch(|| ::day11::day11::solve_part_one(input))
+}
+
+/// Part two with a configurable expansion, the puzzle uses one million.
+#[pyfunction]
+#[pyo3(signature = (input, expansion = 1_000_000))]
+fn solve_part_two(input: &str, expansion: usize) -> PyResult<usize> {
+    crate::catch(|| ::day11::day11::solve_part_two(input, expansion))
+}
+
+pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(solve_part_one, m)?)?;
+    m.add_function(wrap_pyfunction!(solve_part_two, m)?)
+}
@@ -0,0 +1,90 @@
+//! Python bindings for the solvers, built with maturin as the `aoc_2023`
+//! extension module. Every day is a submodule, e.g.
+//! `aoc_2023.day01.solve_part_one(text)`.
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::panic::{self, AssertUnwindSafe};
+
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day08;
+mod day10;
+mod day11;
+
+/// Run a solver, turning a panic on malformed input into a `ValueError`
+/// instead of aborting the interpreter with a `PanicException`.
+fn catch<T>(f: impl FnOnce() -> T) -> PyResult<T> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<String>()
+            .cloned()
+            .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_else(|| "solver panicked".to_string());
+        PyValueError::new_err(message)
+    })
+}
+
+/// Days that only expose their two solvers. Their parsing is private to the
+/// solvers, so there is no parser to bind.
+macro_rules! solver_module {
+    ($day:ident, $part_one:ty, $part_two:ty) => {
+        mod $day {
+            use pyo3::prelude::*;
+
+            #[pyfunction]
+            fn solve_part_one(input: &str) -> PyResult<$part_one> {
+                super::catch(|| ::$day::$day::solve_part_one(input))
+            }
+
+            #[pyfunction]
+            fn solve_part_two(input: &str) -> PyResult<$part_two> {
+                super::catch(|| ::$day::$day::solve_part_two(input))
+            }
+
+            pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
+                m.add_function(wrap_pyfunction!(solve_part_one, m)?)?;
+                m.add_function(wrap_pyfunction!(solve_part_two, m)?)
+            }
+        }
+    };
+}
+
+solver_module!(day01, u32, u32);
+solver_module!(day07, u64, u64);
+solver_module!(day09, i64, i64);
+solver_module!(day12, usize, u64);
+solver_module!(day13, usize, u64);
+solver_module!(day14, usize, usize);
+
+fn add_day(
+    parent: &Bound<'_, PyModule>,
+    name: &str,
+    register: fn(&Bound<'_, PyModule>) -> PyResult<()>,
+) -> PyResult<()> {
+    let module = PyModule::new(parent.py(), name)?;
+    register(&module)?;
+    parent.add_submodule(&module)
+}
+
+#[pymodule]
+#[pyo3(name = "aoc_2023")]
+fn aoc_2023(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    add_day(m, "day01", day01::register)?;
+    add_day(m, "day02", day02::register)?;
+    add_day(m, "day03", day03::register)?;
+    add_day(m, "day04", day04::register)?;
+    add_day(m, "day05", day05::register)?;
+    add_day(m, "day06", day06::register)?;
+    add_day(m, "day07", day07::register)?;
+    add_day(m, "day08", day08::register)?;
+    add_day(m, "day09", day09::register)?;
+    add_day(m, "day10", day10::register)?;
+    add_day(m, "day11", day11::register)?;
+    add_day(m, "day12", day12::register)?;
+    add_day(m, "day13", day13::register)?;
+    add_day(m, "day14", day14::register)
+}
@@ -0,0 +1,44 @@
+"""Run every day against the shared example corpus in ``examples/``.
+
+The ``.answers`` files use the same format as the Rust example tests: one
+``part_one: answer`` / ``part_two(arg): answer`` / ``part_two: skip reason``
+line per expectation.
+"""
+
+from pathlib import Path
+
+import pytest
+
+import aoc_2023
+
+CORPUS = Path(__file__).resolve().parents[2] / "examples"
+
+
+def load_cases():
+    cases = []
+    for day_dir in sorted(CORPUS.iterdir()):
+        for example in sorted(day_dir.glob("*.in")):
+            answers = example.with_suffix(".answers")
+            if not answers.exists():
+                continue
+            for line in answers.read_text().splitlines():
+                line = line.strip()
+                if not line or line.startswith("#"):
+                    continue
+                key, value = (s.strip() for s in line.split(":", 1))
+                part, _, arg = key.partition("(")
+                arg = arg.rstrip(")") or None
+                case_id = f"{day_dir.name}-{example.stem}-{key}"
+                cases.append(pytest.param(day_dir.name, example, part, arg, value, id=case_id))
+    return cases
+
+
+@pytest.mark.parametrize("day, example, part, arg, expected", load_cases())
+def test_example(day, example, part, arg, expected):
+    if expected.startswith("skip"):
+        pytest.skip(expected[len("skip"):].strip() or "skipped")
+
+    solve = getattr(getattr(aoc_2023, day), f"solve_{part}")
+    text = example.read_text()
+    answer = solve(text) if arg is None else solve(text, int(arg))
+    assert str(answer) == expected
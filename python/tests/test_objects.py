@@ -0,0 +1,91 @@
+from pathlib import Path
+
+import pytest
+
+from aoc_2023 import day02, day03, day04, day05, day06, day08, day10, day11
+
+CORPUS = Path(__file__).resolve().parents[2] / "examples"
+
+
+def example(day, name="example"):
+    return (CORPUS / day / f"{name}.in").read_text()
+
+
+def test_seed_maps_translate_in_order():
+    seeds, maps = day05.parse_almanac(example("day05"))
+    assert seeds == [79, 14, 55, 13]
+    assert len(maps) == 7
+    assert isinstance(maps[0], day05.SeedMap)
+
+    locations = []
+    for seed in seeds:
+        for seed_map in maps:
+            seed = seed_map.translate(seed)
+        locations.append(seed)
+    assert locations == [82, 43, 86, 35]
+    assert maps[0](79) == 81
+    with pytest.raises(ValueError, match="line 1"):
+        day05.parse_almanac("seeds: x")
+
+
+def test_schematic():
+    schematic = day03.parse_schematic(example("day03"))
+    assert len(schematic.numbers) == 10
+    assert schematic.numbers[0] == (467, 0, 0, 3)
+    assert schematic.symbols[0] == ("*", 1, 3)
+    assert schematic.numbers_of(0) == [0, 2]
+    assert sum(schematic.part_numbers()) == 4361
+    with pytest.raises(ValueError, match="does not fit"):
+        day03.parse_schematic("9223372036854775808*")
+
+
+def test_scratchcards():
+    cards = day04.parse_scratchcards(example("day04"))
+    assert cards.winners == [4, 2, 2, 1, 0, 0]
+    assert len(cards) == 6
+    assert cards.score() == 13
+    assert cards.total_cards() == 30
+    with pytest.raises(ValueError, match="line 1"):
+        day04.parse_scratchcards("Card 1: 1 2 3")
+
+
+def test_loop_path():
+    path = day10.loop_path(example("day10", "example_two"))
+    assert len(path) == 46
+    assert path.farthest == 23
+    assert path.points[0] == (1, 1)
+    assert (1, 1) in path
+    assert (0, 0) not in path
+
+
+def test_parse_races():
+    assert day06.parse_races(example("day06")) == ([7, 15, 30], [9, 40, 200])
+    assert day06.parse_kerned_race(example("day06")) == (["7", "15", "30"], ["9", "40", "200"])
+
+
+def test_parse_network():
+    directions, locations = day08.parse_network(example("day08", "example_two"))
+    assert directions == "LLR"
+    assert locations[0] == ("AAA", "BBB", "BBB")
+
+
+def test_parse_errors_are_value_errors():
+    with pytest.raises(ValueError, match="line 2, column 10"):
+        day06.parse_races("Time: 7\nDistance 9")
+    with pytest.raises(ValueError):
+        day02.solve_part_one("not a game")
+
+
+def test_expansion_defaults_to_one_million():
+    text = example("day11")
+    assert day11.solve_part_two(text, 10) == 1030
+    assert day11.solve_part_two(text) == 82000210
+
+
+def test_day02_strategies():
+    text = example("day02")
+    for strategy in ["split", "iterator", "bytes"]:
+        assert day02.solve_part_one(text, strategy) == 8
+        assert day02.solve_part_two(text, strategy=strategy) == 2286
+    with pytest.raises(ValueError, match="unknown strategy"):
+        day02.solve_part_one(text, "regex")